@@ -0,0 +1,310 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::Value;
+use std::error::Error;
+use std::fmt;
+
+/// A formatted API catalog, mirroring `template.yaml`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ApiCatalog {
+    pub api_groups: Vec<ApiGroup>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ApiGroup {
+    pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub description: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub base: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub endpoints: Vec<Endpoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Endpoint {
+    pub text: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub description: String,
+    #[serde(
+        default,
+        deserialize_with = "null_as_default",
+        skip_serializing_if = "String::is_empty"
+    )]
+    pub suggested_sentence: String,
+    pub verb: String,
+    pub path: String,
+    /// Overrides the group's base URL when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub parameters: Vec<Parameter>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub description: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub required: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub alternatives: Vec<String>,
+}
+
+/// Treats an explicit `null` like a missing field
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// A field of the document that does not match the catalog schema
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    /// Location in the document, e.g. `api_groups[0].endpoints[3].verb`
+    pub path: String,
+    pub message: String,
+}
+
+/// Every schema mismatch found in a document that parses as YAML
+#[derive(Debug)]
+pub struct CatalogError {
+    pub errors: Vec<FieldError>,
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Formatted YAML does not match the catalog schema ({} errors)",
+            self.errors.len()
+        )?;
+        for error in &self.errors {
+            write!(f, "\n- {}: {}", error.path, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for CatalogError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    String,
+    Bool,
+    List,
+    StringList,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::String => "a string",
+            Kind::Bool => "a boolean",
+            Kind::List => "a list",
+            Kind::StringList => "a list of strings",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::String => value.is_string(),
+            Kind::Bool => value.is_bool(),
+            Kind::List => value.is_sequence(),
+            Kind::StringList => value
+                .as_sequence()
+                .is_some_and(|items| items.iter().all(Value::is_string)),
+        }
+    }
+}
+
+/// (field, expected kind, required)
+const GROUP_FIELDS: &[(&str, Kind, bool)] = &[
+    ("name", Kind::String, true),
+    ("description", Kind::String, false),
+    ("base", Kind::String, false),
+    ("endpoints", Kind::List, true),
+];
+
+const ENDPOINT_FIELDS: &[(&str, Kind, bool)] = &[
+    ("text", Kind::String, true),
+    ("description", Kind::String, false),
+    ("suggested_sentence", Kind::String, false),
+    ("verb", Kind::String, true),
+    ("path", Kind::String, true),
+    ("base", Kind::String, false),
+    ("parameters", Kind::List, false),
+];
+
+const PARAMETER_FIELDS: &[(&str, Kind, bool)] = &[
+    ("name", Kind::String, true),
+    ("description", Kind::String, false),
+    ("required", Kind::Bool, false),
+    ("alternatives", Kind::StringList, false),
+];
+
+impl ApiCatalog {
+    /// Parses a formatted document, reporting every field that does not match the schema
+    pub fn from_yaml(yaml_content: &str) -> Result<Self, Box<dyn Error>> {
+        let document: Value = serde_yaml::from_str(yaml_content)?;
+
+        let errors = schema_errors(&document);
+        if !errors.is_empty() {
+            return Err(Box::new(CatalogError { errors }));
+        }
+
+        Ok(serde_yaml::from_value(document)?)
+    }
+}
+
+fn schema_errors(document: &Value) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    let groups = match document.get("api_groups") {
+        Some(Value::Sequence(groups)) => groups,
+        Some(_) => {
+            errors.push(field_error("api_groups", "expected a list"));
+            return errors;
+        }
+        None => {
+            errors.push(field_error("api_groups", "missing field"));
+            return errors;
+        }
+    };
+
+    for (group_index, group) in groups.iter().enumerate() {
+        let group_path = format!("api_groups[{}]", group_index);
+        if !check_fields(group, &group_path, GROUP_FIELDS, &mut errors) {
+            continue;
+        }
+
+        for (endpoint_index, endpoint) in items(group, "endpoints").iter().enumerate() {
+            let endpoint_path = format!("{}.endpoints[{}]", group_path, endpoint_index);
+            if !check_fields(endpoint, &endpoint_path, ENDPOINT_FIELDS, &mut errors) {
+                continue;
+            }
+
+            for (parameter_index, parameter) in items(endpoint, "parameters").iter().enumerate() {
+                let parameter_path = format!("{}.parameters[{}]", endpoint_path, parameter_index);
+                check_fields(parameter, &parameter_path, PARAMETER_FIELDS, &mut errors);
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks presence and type of each field, returns false when the value is not a mapping
+fn check_fields(
+    value: &Value,
+    path: &str,
+    fields: &[(&str, Kind, bool)],
+    errors: &mut Vec<FieldError>,
+) -> bool {
+    if !value.is_mapping() {
+        errors.push(field_error(path, "expected a mapping"));
+        return false;
+    }
+
+    for (field, kind, required) in fields {
+        let field_path = format!("{}.{}", path, field);
+        match value.get(*field) {
+            None | Some(Value::Null) if *required => {
+                errors.push(field_error(&field_path, "missing field"));
+            }
+            None | Some(Value::Null) => {}
+            Some(field_value) if !kind.matches(field_value) => {
+                errors.push(field_error(
+                    &field_path,
+                    &format!("expected {}, found {}", kind.name(), describe(field_value)),
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    true
+}
+
+fn items<'a>(value: &'a Value, field: &str) -> &'a [Value] {
+    value
+        .get(field)
+        .and_then(Value::as_sequence)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => format!("boolean {}", b),
+        Value::Number(n) => format!("number {}", n),
+        Value::String(s) => format!("string \"{}\"", s),
+        Value::Sequence(_) => "a list".to_string(),
+        Value::Mapping(_) => "a mapping".to_string(),
+        Value::Tagged(_) => "a tagged value".to_string(),
+    }
+}
+
+fn field_error(path: &str, message: &str) -> FieldError {
+    FieldError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formatted_sample() {
+        let yaml = std::fs::read_to_string("samples/output.yaml").unwrap();
+        let catalog = ApiCatalog::from_yaml(&yaml).unwrap();
+
+        assert_eq!(catalog.api_groups[0].name, "User Service");
+        let endpoint = &catalog.api_groups[0].endpoints[0];
+        assert_eq!(endpoint.path, "/users/{id}");
+        assert_eq!(
+            endpoint.parameters[0].alternatives,
+            vec!["user_id", "userId"]
+        );
+    }
+
+    #[test]
+    fn reports_every_mismatched_field_with_its_path() {
+        let yaml = "\
+api_groups:
+  - name: Users
+    endpoints:
+      - text: Get user
+        path: /users/{id}
+        parameters:
+          - name: id
+            required: \"yes\"
+      - text: List users
+        verb: GET
+        path: /users
+        description: null
+  - description: no name
+    endpoints: []
+";
+        let err = ApiCatalog::from_yaml(yaml).unwrap_err();
+        let errors = &err.downcast_ref::<CatalogError>().unwrap().errors;
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "api_groups[0].endpoints[0].verb",
+                "api_groups[0].endpoints[0].parameters[0].required",
+                "api_groups[1].name",
+            ]
+        );
+        assert_eq!(
+            errors[1].message,
+            "expected a boolean, found string \"yes\""
+        );
+    }
+}
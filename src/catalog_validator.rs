@@ -0,0 +1,275 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::catalog::{ApiCatalog, Endpoint};
+use crate::sentence_coverage;
+
+const HTTP_METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A rule violation in a catalog that otherwise matches the schema
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Location in the document, e.g. `api_groups[0].endpoints[3].verb`
+    pub path: String,
+    pub message: String,
+}
+
+/// Checks the semantic rules every formatted endpoint must follow
+pub fn validate_catalog(catalog: &ApiCatalog) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    // Normalized endpoint text -> path of its first occurrence
+    let mut seen_texts: HashMap<String, String> = HashMap::new();
+
+    for (group_index, group) in catalog.api_groups.iter().enumerate() {
+        let group_path = format!("api_groups[{}]", group_index);
+        check_base(&group.base, &format!("{}.base", group_path), &mut issues);
+
+        for (endpoint_index, endpoint) in group.endpoints.iter().enumerate() {
+            let endpoint_path = format!("{}.endpoints[{}]", group_path, endpoint_index);
+
+            if !HTTP_METHODS.contains(&endpoint.verb.trim().to_uppercase().as_str()) {
+                issues.push(issue(
+                    Severity::Error,
+                    format!("{}.verb", endpoint_path),
+                    format!("'{}' is not an HTTP method", endpoint.verb),
+                ));
+            }
+
+            if let Some(base) = &endpoint.base {
+                check_base(base, &format!("{}.base", endpoint_path), &mut issues);
+            }
+
+            let text = endpoint.text.trim().to_lowercase();
+            match seen_texts.get(&text) {
+                Some(first) => issues.push(issue(
+                    Severity::Error,
+                    format!("{}.text", endpoint_path),
+                    format!("'{}' is already used by {}", endpoint.text, first),
+                )),
+                None => {
+                    seen_texts.insert(text, endpoint_path.clone());
+                }
+            }
+
+            check_placeholders(endpoint, &endpoint_path, &mut issues);
+            check_alternatives(endpoint, &endpoint_path, &mut issues);
+
+            for problem in sentence_coverage::sentence_problems(endpoint) {
+                issues.push(issue(
+                    Severity::Warning,
+                    format!("{}.suggested_sentence", endpoint_path),
+                    problem,
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// A literal part of a path or a placeholder
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathPart<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Splits a path around its `{placeholders}`. Braces around an empty name
+/// or another opening brace, as in `{x{y}`, are literal text.
+pub fn path_parts(path: &str) -> Vec<PathPart<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        let name = rest[start + 1..start + length].trim();
+        if name.is_empty() || name.contains('{') {
+            parts.push(PathPart::Literal(&rest[..start + 1]));
+            rest = &rest[start + 1..];
+            continue;
+        }
+        if start > 0 {
+            parts.push(PathPart::Literal(&rest[..start]));
+        }
+        parts.push(PathPart::Placeholder(name));
+        rest = &rest[start + length + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(PathPart::Literal(rest));
+    }
+    parts
+}
+
+/// Names of the `{placeholder}` segments of a path, as `path_parts` finds them
+pub fn path_placeholders(path: &str) -> Vec<&str> {
+    path_parts(path)
+        .into_iter()
+        .filter_map(|part| match part {
+            PathPart::Placeholder(name) => Some(name),
+            PathPart::Literal(_) => None,
+        })
+        .collect()
+}
+
+fn check_base(base: &str, path: &str, issues: &mut Vec<ValidationIssue>) {
+    let base = base.trim();
+    if base.is_empty() {
+        return;
+    }
+    match url::Url::parse(base) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {}
+        Ok(url) => issues.push(issue(
+            Severity::Error,
+            path.to_string(),
+            format!("'{}' uses unsupported scheme '{}'", base, url.scheme()),
+        )),
+        Err(e) => issues.push(issue(
+            Severity::Error,
+            path.to_string(),
+            format!("'{}' is not a valid URL: {}", base, e),
+        )),
+    }
+}
+
+fn check_placeholders(endpoint: &Endpoint, endpoint_path: &str, issues: &mut Vec<ValidationIssue>) {
+    for placeholder in path_placeholders(&endpoint.path) {
+        match endpoint
+            .parameters
+            .iter()
+            .position(|parameter| parameter.name == placeholder)
+        {
+            None => issues.push(issue(
+                Severity::Error,
+                format!("{}.path", endpoint_path),
+                format!("placeholder {{{}}} has no matching parameter", placeholder),
+            )),
+            Some(index) if !endpoint.parameters[index].required => issues.push(issue(
+                Severity::Error,
+                format!("{}.parameters[{}].required", endpoint_path, index),
+                format!("path parameter '{}' must be required", placeholder),
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_alternatives(endpoint: &Endpoint, endpoint_path: &str, issues: &mut Vec<ValidationIssue>) {
+    for (index, parameter) in endpoint.parameters.iter().enumerate() {
+        if parameter.alternatives.iter().any(|alternative| {
+            alternative
+                .trim()
+                .eq_ignore_ascii_case(parameter.name.trim())
+        }) {
+            issues.push(issue(
+                Severity::Warning,
+                format!("{}.parameters[{}].alternatives", endpoint_path, index),
+                format!(
+                    "alternatives repeat the parameter name '{}'",
+                    parameter.name
+                ),
+            ));
+        }
+    }
+}
+
+fn issue(severity: Severity, path: String, message: String) -> ValidationIssue {
+    ValidationIssue {
+        severity,
+        path,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatted_sample_has_no_errors() {
+        let yaml = std::fs::read_to_string("samples/output.yaml").unwrap();
+        let catalog = ApiCatalog::from_yaml(&yaml).unwrap();
+        let errors: Vec<ValidationIssue> = validate_catalog(&catalog)
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect();
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn reports_each_rule_with_its_path() {
+        let yaml = "\
+api_groups:
+  - name: Users
+    base: not a url
+    endpoints:
+      - text: Get user
+        verb: FETCH
+        path: /users/{id}/orders/{orderId}
+        parameters:
+          - name: id
+            required: false
+            alternatives: [ID, user_id]
+      - text: get user
+        verb: get
+        path: /users
+";
+        let catalog = ApiCatalog::from_yaml(yaml).unwrap();
+        let issues = validate_catalog(&catalog);
+        let issues: Vec<(Severity, &str)> = issues
+            .iter()
+            .map(|issue| (issue.severity, issue.path.as_str()))
+            .collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                (Severity::Error, "api_groups[0].base"),
+                (Severity::Error, "api_groups[0].endpoints[0].verb"),
+                (
+                    Severity::Error,
+                    "api_groups[0].endpoints[0].parameters[0].required"
+                ),
+                (Severity::Error, "api_groups[0].endpoints[0].path"),
+                (
+                    Severity::Warning,
+                    "api_groups[0].endpoints[0].parameters[0].alternatives"
+                ),
+                (
+                    Severity::Warning,
+                    "api_groups[0].endpoints[0].suggested_sentence"
+                ),
+                (Severity::Error, "api_groups[0].endpoints[1].text"),
+                (
+                    Severity::Warning,
+                    "api_groups[0].endpoints[1].suggested_sentence"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_paths_around_placeholders() {
+        assert_eq!(
+            path_parts("/users/{ id }/a{x{y}"),
+            vec![
+                PathPart::Literal("/users/"),
+                PathPart::Placeholder("id"),
+                PathPart::Literal("/a{"),
+                PathPart::Literal("x"),
+                PathPart::Placeholder("y"),
+            ]
+        );
+        assert_eq!(path_placeholders("/{}/{ id }/{x{y}"), vec!["id", "y"]);
+    }
+}
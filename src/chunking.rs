@@ -0,0 +1,256 @@
+use graflog::app_log;
+use serde_yaml::{Mapping, Value};
+use std::env;
+use std::error::Error;
+
+/// Settings for formatting large inputs a few endpoints at a time
+#[derive(Clone, Debug)]
+pub struct ChunkingOptions {
+    pub endpoints_per_chunk: usize,
+    /// How many chunks are sent to the provider concurrently
+    pub parallelism: usize,
+}
+
+impl ChunkingOptions {
+    /// Reads FORMAT_CHUNK_ENDPOINTS and FORMAT_CHUNK_PARALLELISM, chunking is off when the former is unset
+    pub fn from_env() -> Result<Option<Self>, Box<dyn Error>> {
+        let endpoints_per_chunk = match env::var("FORMAT_CHUNK_ENDPOINTS") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid FORMAT_CHUNK_ENDPOINTS '{}': {}", value, e))?,
+            _ => return Ok(None),
+        };
+        if endpoints_per_chunk == 0 {
+            return Ok(None);
+        }
+
+        let parallelism = match env::var("FORMAT_CHUNK_PARALLELISM") {
+            Ok(value) => value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid FORMAT_CHUNK_PARALLELISM '{}': {}", value, e))?
+                .max(1),
+            Err(_) => 1,
+        };
+
+        Ok(Some(ChunkingOptions {
+            endpoints_per_chunk,
+            parallelism,
+        }))
+    }
+}
+
+/// Splits a structured (YAML or JSON) input into documents holding at most
+/// `endpoints_per_chunk` endpoints each.
+///
+/// Two shapes are understood: a flat list of endpoints (`endpoints:` or a
+/// top-level sequence) and grouped endpoints (`api_groups[*].endpoints`), in
+/// which case every chunk repeats the group header of the endpoints it holds.
+/// Anything else is returned as a single chunk.
+pub fn split_input(input_content: &str, endpoints_per_chunk: usize) -> Vec<String> {
+    let single = vec![input_content.to_string()];
+
+    let document = match serde_yaml::from_str::<Value>(input_content) {
+        Ok(document) => document,
+        Err(_) => return single,
+    };
+
+    let chunks = match &document {
+        Value::Sequence(items) => items
+            .chunks(endpoints_per_chunk)
+            .map(|chunk| Value::Sequence(chunk.to_vec()))
+            .collect::<Vec<_>>(),
+        Value::Mapping(root) => match largest_sequence_key(root) {
+            Some(key) => {
+                let items = root[&key].as_sequence().cloned().unwrap_or_default();
+                let item_chunks = if items.iter().any(is_group) {
+                    split_groups(&items, endpoints_per_chunk)
+                } else {
+                    items
+                        .chunks(endpoints_per_chunk)
+                        .map(|chunk| chunk.to_vec())
+                        .collect()
+                };
+                item_chunks
+                    .into_iter()
+                    .map(|chunk| {
+                        let mut mapping = root.clone();
+                        mapping.insert(key.clone(), Value::Sequence(chunk));
+                        Value::Mapping(mapping)
+                    })
+                    .collect()
+            }
+            None => return single,
+        },
+        _ => return single,
+    };
+
+    if chunks.len() <= 1 {
+        return single;
+    }
+
+    let serialized: Result<Vec<String>, _> = chunks.iter().map(serde_yaml::to_string).collect();
+    match serialized {
+        Ok(serialized) => serialized,
+        Err(e) => {
+            app_log!(
+                warn,
+                "Could not serialize input chunks, sending input whole: {}",
+                e
+            );
+            single
+        }
+    }
+}
+
+/// Merges the `api_groups` of several formatted documents into one document.
+/// Groups are matched by name and keep the order in which they first appear;
+/// endpoints keep the order of the chunks they came from.
+pub fn merge_api_groups(documents: &[String]) -> Result<String, Box<dyn Error>> {
+    let mut merged: Vec<Value> = Vec::new();
+
+    for (index, document) in documents.iter().enumerate() {
+        let value: Value = serde_yaml::from_str(document)?;
+        let groups = value
+            .get("api_groups")
+            .and_then(Value::as_sequence)
+            .ok_or_else(|| format!("Formatted chunk {} has no api_groups list", index + 1))?;
+
+        for group in groups {
+            let name = group.get("name").cloned();
+            let existing = merged
+                .iter_mut()
+                .find(|candidate| name.is_some() && candidate.get("name") == name.as_ref());
+
+            match existing {
+                Some(existing) => {
+                    let endpoints = group
+                        .get("endpoints")
+                        .and_then(Value::as_sequence)
+                        .cloned()
+                        .unwrap_or_default();
+                    if let Some(Value::Sequence(target)) = existing.get_mut("endpoints") {
+                        target.extend(endpoints);
+                    } else if let Value::Mapping(mapping) = existing {
+                        mapping.insert(Value::from("endpoints"), Value::Sequence(endpoints));
+                    }
+                }
+                None => merged.push(group.clone()),
+            }
+        }
+    }
+
+    let mut root = Mapping::new();
+    root.insert(Value::from("api_groups"), Value::Sequence(merged));
+    Ok(serde_yaml::to_string(&Value::Mapping(root))?)
+}
+
+pub fn largest_sequence_key(root: &Mapping) -> Option<Value> {
+    root.iter()
+        .filter_map(|(key, value)| value.as_sequence().map(|items| (key, items.len())))
+        .max_by_key(|(_, len)| *len)
+        .map(|(key, _)| key.clone())
+}
+
+pub fn is_group(item: &Value) -> bool {
+    item.get("endpoints").is_some_and(Value::is_sequence)
+}
+
+fn split_groups(groups: &[Value], endpoints_per_chunk: usize) -> Vec<Vec<Value>> {
+    let mut chunks: Vec<Vec<Value>> = Vec::new();
+    let mut current: Vec<Value> = Vec::new();
+    let mut current_count = 0;
+
+    for group in groups {
+        let endpoints = group
+            .get("endpoints")
+            .and_then(Value::as_sequence)
+            .cloned()
+            .unwrap_or_default();
+
+        if endpoints.is_empty() {
+            current.push(group.clone());
+            continue;
+        }
+
+        let mut remaining = endpoints.as_slice();
+        while !remaining.is_empty() {
+            if current_count == endpoints_per_chunk {
+                chunks.push(std::mem::take(&mut current));
+                current_count = 0;
+            }
+            let take = remaining.len().min(endpoints_per_chunk - current_count);
+            let mut part = group.clone();
+            if let Value::Mapping(mapping) = &mut part {
+                mapping.insert(
+                    Value::from("endpoints"),
+                    Value::Sequence(remaining[..take].to_vec()),
+                );
+            }
+            current.push(part);
+            current_count += take;
+            remaining = &remaining[take..];
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_flat_endpoint_list() {
+        let input = std::fs::read_to_string("samples/gpecs.yaml").unwrap();
+        let total = serde_yaml::from_str::<Value>(&input).unwrap()["endpoints"]
+            .as_sequence()
+            .unwrap()
+            .len();
+
+        let chunks = split_input(&input, 10);
+        assert_eq!(chunks.len(), total.div_ceil(10));
+
+        let rejoined: usize = chunks
+            .iter()
+            .map(|chunk| {
+                serde_yaml::from_str::<Value>(chunk).unwrap()["endpoints"]
+                    .as_sequence()
+                    .unwrap()
+                    .len()
+            })
+            .sum();
+        assert_eq!(rejoined, total);
+    }
+
+    #[test]
+    fn leaves_free_text_whole() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert_eq!(split_input(&input, 1), vec![input]);
+    }
+
+    #[test]
+    fn merges_groups_by_name_in_order() {
+        let first = "api_groups:\n  - name: A\n    endpoints:\n      - text: a1\n  - name: B\n    endpoints:\n      - text: b1\n";
+        let second = "api_groups:\n  - name: B\n    endpoints:\n      - text: b2\n  - name: C\n    endpoints: []\n";
+
+        let merged: Value =
+            serde_yaml::from_str(&merge_api_groups(&[first.into(), second.into()]).unwrap())
+                .unwrap();
+        let groups = merged["api_groups"].as_sequence().unwrap();
+
+        let names: Vec<&str> = groups.iter().map(|g| g["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+        let b_endpoints: Vec<&str> = groups[1]["endpoints"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|e| e["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(b_endpoints, vec!["b1", "b2"]);
+    }
+}
@@ -0,0 +1,200 @@
+use graflog::app_log;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashSet;
+use std::env;
+use std::error::Error;
+
+use crate::chunking::{is_group, largest_sequence_key};
+
+/// Outcome of comparing the endpoints of the input with the formatted catalog
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Reconciliation {
+    pub input_endpoints: usize,
+    pub output_endpoints: usize,
+    /// Endpoints added by re-prompting for the missing ones
+    pub recovered: usize,
+    /// Labels of input endpoints still absent from the output
+    pub missing: Vec<String>,
+}
+
+/// Number of re-prompts for missing endpoints, read from FORMAT_COMPLETENESS_RETRIES (default 1)
+pub fn retries_from_env() -> Result<usize, Box<dyn Error>> {
+    match env::var("FORMAT_COMPLETENESS_RETRIES") {
+        Ok(value) => Ok(value
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid FORMAT_COMPLETENESS_RETRIES '{}': {}", value, e))?),
+        Err(_) => Ok(1),
+    }
+}
+
+/// Endpoints found in a structured input, either a flat list (`endpoints:` or a
+/// top-level sequence) or `api_groups[*].endpoints`. Grouped endpoints get a
+/// `group` field so a re-prompt can put them back in the right group.
+/// Returns None when the input is free text and endpoints cannot be counted.
+pub fn input_endpoints(input_content: &str) -> Option<Vec<Value>> {
+    let document = serde_yaml::from_str::<Value>(input_content).ok()?;
+
+    let items = match &document {
+        Value::Sequence(items) => items.clone(),
+        Value::Mapping(root) => root[&largest_sequence_key(root)?].as_sequence()?.clone(),
+        _ => return None,
+    };
+
+    if !items.iter().any(is_group) {
+        return Some(items.into_iter().filter(Value::is_mapping).collect());
+    }
+
+    let mut endpoints = Vec::new();
+    for group in &items {
+        let group_name = group.get("name").cloned();
+        for endpoint in group
+            .get("endpoints")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+        {
+            let mut endpoint = endpoint.clone();
+            if let (Value::Mapping(mapping), Some(name)) = (&mut endpoint, &group_name) {
+                mapping.insert(Value::from("group"), name.clone());
+            }
+            endpoints.push(endpoint);
+        }
+    }
+    Some(endpoints)
+}
+
+/// All endpoints of a formatted `api_groups` document
+pub fn output_endpoints(formatted_yaml: &str) -> Result<Vec<Value>, Box<dyn Error>> {
+    let document: Value = serde_yaml::from_str(formatted_yaml)?;
+    Ok(document
+        .get("api_groups")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .flat_map(|group| {
+            group
+                .get("endpoints")
+                .and_then(Value::as_sequence)
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect())
+}
+
+/// Input endpoints with no counterpart in the output.
+///
+/// Only reported when the output has fewer endpoints than the input, so an
+/// endpoint the model merely renamed is not re-requested as a duplicate.
+pub fn missing_endpoints(expected: &[Value], formatted: &[Value]) -> Vec<Value> {
+    if formatted.len() >= expected.len() {
+        return Vec::new();
+    }
+
+    let known: HashSet<String> = formatted.iter().flat_map(identity_keys).collect();
+    expected
+        .iter()
+        .filter(|endpoint| {
+            !identity_keys(endpoint)
+                .iter()
+                .any(|key| known.contains(key))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Input document listing only the given endpoints, used to re-prompt for them
+pub fn missing_input(missing: &[Value]) -> Result<String, Box<dyn Error>> {
+    let mut root = Mapping::new();
+    root.insert(Value::from("endpoints"), Value::Sequence(missing.to_vec()));
+    Ok(serde_yaml::to_string(&Value::Mapping(root))?)
+}
+
+/// Human readable label of an input endpoint for logs and reports
+pub fn endpoint_label(endpoint: &Value) -> String {
+    ["id", "text", "name", "path"]
+        .iter()
+        .find_map(|field| endpoint.get(*field).and_then(Value::as_str))
+        .unwrap_or("<unnamed endpoint>")
+        .to_string()
+}
+
+impl Reconciliation {
+    pub fn log(&self) {
+        if self.missing.is_empty() {
+            app_log!(
+                info,
+                "Endpoint check: {} in input, {} formatted ({} recovered)",
+                self.input_endpoints,
+                self.output_endpoints,
+                self.recovered
+            );
+        } else {
+            app_log!(
+                warn,
+                "Endpoint check: {} in input, {} formatted, still missing: {}",
+                self.input_endpoints,
+                self.output_endpoints,
+                self.missing.join(", ")
+            );
+        }
+    }
+}
+
+/// Normalized names an endpoint can be recognized by (id, text, name, verb + path)
+fn identity_keys(endpoint: &Value) -> Vec<String> {
+    let mut keys: Vec<String> = ["id", "text", "name"]
+        .iter()
+        .filter_map(|field| endpoint.get(*field).and_then(Value::as_str))
+        .map(normalize)
+        .filter(|key| !key.is_empty())
+        .collect();
+
+    if let Some(path) = endpoint.get("path").and_then(Value::as_str) {
+        let verb = endpoint
+            .get("verb")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_uppercase();
+        keys.push(format!("{} {}", verb, path.trim()));
+    }
+    keys
+}
+
+fn normalize(label: &str) -> String {
+    label
+        .trim()
+        .to_lowercase()
+        .replace(['_', '-'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_endpoints_left_out_of_the_output() {
+        let input = std::fs::read_to_string("samples/sample_endpoints.yaml").unwrap();
+        let expected = input_endpoints(&input).unwrap();
+        assert_eq!(expected.len(), 2);
+
+        let formatted = output_endpoints(
+            "api_groups:\n  - name: Documents\n    endpoints:\n      - text: Search documents\n        verb: GET\n        path: /search\n",
+        )
+        .unwrap();
+
+        let missing = missing_endpoints(&expected, &formatted);
+        let labels: Vec<String> = missing.iter().map(endpoint_label).collect();
+        assert_eq!(labels, vec!["custom_export"]);
+    }
+
+    #[test]
+    fn free_text_input_is_not_counted() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert!(input_endpoints(&input).is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use serde_yaml::{Mapping, Value};
+use std::error::Error;
+
+use crate::catalog::{ApiCatalog, Endpoint};
+
+/// An endpoint of a natively imported catalog with fields left for the LLM
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnrichmentTarget {
+    pub group: usize,
+    pub endpoint: usize,
+    pub fields: Vec<&'static str>,
+}
+
+/// Fields of an endpoint that a native import left blank, among `allowed`
+pub fn blank_fields(endpoint: &Endpoint, allowed: &[&'static str]) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if endpoint.verb.trim().is_empty() {
+        fields.push("verb");
+    }
+    if endpoint.path.trim().is_empty() {
+        fields.push("path");
+    }
+    if endpoint.description.trim().is_empty() {
+        fields.push("description");
+    }
+    if endpoint.suggested_sentence.trim().is_empty() {
+        fields.push("suggested_sentence");
+    }
+    if endpoint
+        .parameters
+        .iter()
+        .any(|parameter| parameter.alternatives.is_empty())
+    {
+        fields.push("alternatives");
+    }
+    fields.retain(|field| allowed.contains(field));
+    fields
+}
+
+/// Every endpoint with blank `allowed` fields, in document order
+pub fn targets(catalog: &ApiCatalog, allowed: &[&'static str]) -> Vec<EnrichmentTarget> {
+    let mut targets = Vec::new();
+    for (group_index, group) in catalog.api_groups.iter().enumerate() {
+        for (endpoint_index, endpoint) in group.endpoints.iter().enumerate() {
+            let fields = blank_fields(endpoint, allowed);
+            if !fields.is_empty() {
+                targets.push(EnrichmentTarget {
+                    group: group_index,
+                    endpoint: endpoint_index,
+                    fields,
+                });
+            }
+        }
+    }
+    targets
+}
+
+/// Input for the enrichment prompt: the target endpoints numbered by their
+/// position in `targets`, each with the list of fields to fill
+pub fn enrichment_input(
+    catalog: &ApiCatalog,
+    targets: &[EnrichmentTarget],
+) -> Result<String, Box<dyn Error>> {
+    let mut items = Vec::new();
+    for (id, target) in targets.iter().enumerate() {
+        let endpoint = &catalog.api_groups[target.group].endpoints[target.endpoint];
+        let mut item = Mapping::new();
+        item.insert(Value::from("id"), Value::from(id));
+        item.insert(Value::from("text"), Value::from(endpoint.text.as_str()));
+        item.insert(
+            Value::from("description"),
+            Value::from(endpoint.description.as_str()),
+        );
+        item.insert(Value::from("verb"), Value::from(endpoint.verb.as_str()));
+        item.insert(Value::from("path"), Value::from(endpoint.path.as_str()));
+        item.insert(
+            Value::from("parameters"),
+            serde_yaml::to_value(&endpoint.parameters)?,
+        );
+        item.insert(Value::from("fill"), serde_yaml::to_value(&target.fields)?);
+        items.push(Value::Mapping(item));
+    }
+    Ok(serde_yaml::to_string(&Value::Sequence(items))?)
+}
+
+/// Copies the answered fields into the catalog, never overwriting a value
+/// the import produced. Returns the number of endpoints that changed.
+pub fn apply_enrichment(
+    catalog: &mut ApiCatalog,
+    targets: &[EnrichmentTarget],
+    answer_yaml: &str,
+) -> Result<usize, Box<dyn Error>> {
+    let answer: Value = serde_yaml::from_str(answer_yaml)?;
+    let answers = answer
+        .as_sequence()
+        .ok_or("Enrichment answer is not a list")?;
+
+    let mut changed = 0;
+    for item in answers {
+        let target = match item
+            .get("id")
+            .and_then(Value::as_u64)
+            .and_then(|id| targets.get(id as usize))
+        {
+            Some(target) => target,
+            None => continue,
+        };
+        let endpoint = &mut catalog.api_groups[target.group].endpoints[target.endpoint];
+        if fill_endpoint(endpoint, target, item) {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+fn fill_endpoint(endpoint: &mut Endpoint, target: &EnrichmentTarget, item: &Value) -> bool {
+    let mut changed = false;
+
+    if target.fields.contains(&"verb") && endpoint.verb.is_empty() {
+        if let Some(verb) = item.get("verb").and_then(Value::as_str) {
+            endpoint.verb = verb.trim().to_uppercase();
+            changed = true;
+        }
+    }
+    if target.fields.contains(&"path") && endpoint.path.is_empty() {
+        if let Some(path) = item.get("path").and_then(Value::as_str) {
+            let path = path.trim();
+            endpoint.path = if path.starts_with('/') {
+                path.to_string()
+            } else {
+                format!("/{}", path)
+            };
+            changed = true;
+        }
+    }
+
+    if target.fields.contains(&"description") && endpoint.description.is_empty() {
+        if let Some(description) = item.get("description").and_then(Value::as_str) {
+            endpoint.description = description.trim().to_string();
+            changed = true;
+        }
+    }
+
+    if target.fields.contains(&"suggested_sentence") && endpoint.suggested_sentence.is_empty() {
+        if let Some(sentence) = item.get("suggested_sentence").and_then(Value::as_str) {
+            endpoint.suggested_sentence = sentence.trim().to_string();
+            changed = true;
+        }
+    }
+
+    // Alternatives come back as a mapping of parameter name to names
+    if target.fields.contains(&"alternatives") {
+        let answered = item.get("alternatives").and_then(Value::as_mapping);
+        for parameter in endpoint
+            .parameters
+            .iter_mut()
+            .filter(|parameter| parameter.alternatives.is_empty())
+        {
+            let alternatives: Vec<String> = answered
+                .and_then(|answered| answered.get(parameter.name.as_str()))
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(|alternative| alternative.trim().to_string())
+                .filter(|alternative| {
+                    !alternative.is_empty() && !alternative.eq_ignore_ascii_case(&parameter.name)
+                })
+                .collect();
+            if !alternatives.is_empty() {
+                parameter.alternatives = alternatives;
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_only_blank_fields() {
+        let mut catalog = ApiCatalog::from_yaml(
+            "api_groups:\n  - name: Pets\n    endpoints:\n      - text: Get pet\n        verb: GET\n        path: /pets/{petId}\n        parameters:\n          - name: petId\n            required: true\n          - name: expand\n            alternatives: [include]\n      - text: List pets\n        suggested_sentence: List all pets\n        verb: GET\n        path: /pets\n",
+        )
+        .unwrap();
+        let targets = targets(&catalog, &["suggested_sentence", "alternatives"]);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(
+            targets[0].fields,
+            vec!["suggested_sentence", "alternatives"]
+        );
+
+        let answer = "- id: 0\n  suggested_sentence: Get pet id 7\n  alternatives:\n    petId: [pet_id, petId, id]\n    expand: [with]\n";
+        assert_eq!(apply_enrichment(&mut catalog, &targets, answer).unwrap(), 1);
+
+        let endpoint = &catalog.api_groups[0].endpoints[0];
+        assert_eq!(endpoint.suggested_sentence, "Get pet id 7");
+        assert_eq!(endpoint.parameters[0].alternatives, vec!["pet_id", "id"]);
+        assert_eq!(endpoint.parameters[1].alternatives, vec!["include"]);
+    }
+
+    #[test]
+    fn infers_only_missing_verbs_and_paths() {
+        let mut catalog = ApiCatalog::from_yaml(
+            "api_groups:\n  - name: Default\n    endpoints:\n      - text: List actions\n        suggested_sentence: List actions\n        verb: GET\n        path: \"\"\n",
+        )
+        .unwrap();
+        let targets = targets(&catalog, &["verb", "path"]);
+        assert_eq!(targets[0].fields, vec!["path"]);
+
+        let answer = "- id: 0\n  verb: post\n  path: actions\n";
+        apply_enrichment(&mut catalog, &targets, answer).unwrap();
+
+        let endpoint = &catalog.api_groups[0].endpoints[0];
+        assert_eq!(
+            (endpoint.verb.as_str(), endpoint.path.as_str()),
+            ("GET", "/actions")
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use std::error::Error;
+use std::io::Write;
+
+use crate::catalog::{ApiCatalog, Endpoint};
+use crate::catalog_validator::path_placeholders;
+use crate::exporters::has_body;
+
+/// The generators fill the placeholders `path_placeholders` finds
+pub use crate::catalog_validator::{path_parts, PathPart};
+
+pub mod rust;
+pub mod typescript;
+
+/// Where an argument of a generated method is sent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Location {
+    Path,
+    Query,
+    Body,
+}
+
+/// A group of the catalog, generated as one client type
+pub struct ClientGroup<'a> {
+    /// Lowercase words of the group name, unique across the catalog
+    pub words: Vec<String>,
+    pub description: &'a str,
+    pub base: &'a str,
+    pub operations: Vec<Operation<'a>>,
+}
+
+/// An endpoint, generated as one method and its parameters type
+pub struct Operation<'a> {
+    pub endpoint: &'a Endpoint,
+    /// Lowercase words of the endpoint text, unique across the catalog
+    pub words: Vec<String>,
+    /// Base URL of the endpoint when it overrides its group's
+    pub base: Option<&'a str>,
+    pub method: String,
+    /// Path with `{placeholders}`, starting with `/`
+    pub path: String,
+    pub fields: Vec<Field>,
+}
+
+pub struct Field {
+    /// Name sent to the API
+    pub name: String,
+    pub description: String,
+    /// Lowercase words of the name, unique within the endpoint
+    pub words: Vec<String>,
+    pub location: Location,
+    /// Placeholders are required whatever the catalog says
+    pub required: bool,
+}
+
+/// Methods the generated clients can send, other verbs are left out
+const METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// The catalog arranged for the generators, with names made unique
+pub fn client_groups(catalog: &ApiCatalog) -> Vec<ClientGroup<'_>> {
+    let mut group_names: Vec<Vec<String>> = Vec::new();
+    let mut operation_names: Vec<Vec<String>> = Vec::new();
+
+    catalog
+        .api_groups
+        .iter()
+        .map(|group| ClientGroup {
+            words: unique_words(words(&group.name, "api"), &mut group_names),
+            description: group.description.trim(),
+            base: group.base.trim(),
+            operations: group
+                .endpoints
+                .iter()
+                .filter(|endpoint| METHODS.contains(&endpoint.verb.trim().to_uppercase().as_str()))
+                .map(|endpoint| Operation {
+                    endpoint,
+                    words: unique_words(words(&endpoint.text, "call"), &mut operation_names),
+                    base: endpoint
+                        .base
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|base| !base.is_empty() && *base != group.base.trim()),
+                    method: endpoint.verb.trim().to_uppercase(),
+                    path: format!("/{}", endpoint.path.trim().trim_start_matches('/')),
+                    fields: fields(endpoint),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Parameters of an endpoint, and placeholders of its path that have none
+fn fields(endpoint: &Endpoint) -> Vec<Field> {
+    let placeholders = path_placeholders(&endpoint.path);
+    let in_body = has_body(&endpoint.verb);
+    let mut taken: Vec<Vec<String>> = Vec::new();
+    let mut fields: Vec<Field> = Vec::new();
+
+    let parameters = endpoint
+        .parameters
+        .iter()
+        .map(|parameter| {
+            (
+                parameter.name.trim(),
+                parameter.description.trim(),
+                parameter.required,
+            )
+        })
+        .chain(
+            placeholders
+                .iter()
+                .map(|placeholder| (*placeholder, "", true)),
+        );
+    for (name, description, required) in parameters {
+        if name.is_empty() || fields.iter().any(|field| field.name == name) {
+            continue;
+        }
+        let in_path = placeholders.contains(&name);
+        fields.push(Field {
+            name: name.to_string(),
+            description: description.to_string(),
+            words: unique_words(words(name, "value"), &mut taken),
+            location: if in_path {
+                Location::Path
+            } else if in_body {
+                Location::Body
+            } else {
+                Location::Query
+            },
+            required: in_path || required,
+        });
+    }
+    fields
+}
+
+/// Field a placeholder of the operation path is filled from
+pub fn placeholder_field<'a>(
+    operation: &'a Operation,
+    name: &str,
+) -> Result<&'a Field, Box<dyn Error>> {
+    operation
+        .fields
+        .iter()
+        .find(|field| field.location == Location::Path && field.name == name)
+        .ok_or_else(|| {
+            format!(
+                "Placeholder '{}' of {} {} has no parameter",
+                name, operation.method, operation.path
+            )
+            .into()
+        })
+}
+
+/// A zip with the Rust crate under `rust/` and the TypeScript module under
+/// `typescript/`
+pub fn clients_zip(catalog: &ApiCatalog) -> Result<Vec<u8>, Box<dyn Error>> {
+    let groups = client_groups(catalog);
+    let files = [
+        ("rust/Cargo.toml", rust::cargo_toml()),
+        ("rust/src/lib.rs", rust::generate(&groups)?),
+        ("typescript/client.ts", typescript::generate(&groups)?),
+    ];
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in files {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Lowercase ASCII words of a name: `getUserById` and `get_user_by_id` both
+/// give get, user, by, id
+fn words(name: &str, fallback: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            previous_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    if words.is_empty() {
+        words.push(fallback.to_string());
+    }
+    words
+}
+
+/// Numbers the words when another name already has them
+fn unique_words(words: Vec<String>, taken: &mut Vec<Vec<String>>) -> Vec<String> {
+    let mut unique = words.clone();
+    let mut count = 1;
+    while taken.contains(&unique) {
+        count += 1;
+        unique = words.clone();
+        unique.push(count.to_string());
+    }
+    taken.push(unique.clone());
+    unique
+}
+
+pub fn snake_case(words: &[String]) -> String {
+    identifier(words.join("_"))
+}
+
+pub fn pascal_case(words: &[String]) -> String {
+    identifier(words.iter().map(|word| capitalize(word)).collect())
+}
+
+pub fn camel_case(words: &[String]) -> String {
+    let mut name = words[0].clone();
+    name.extend(words[1..].iter().map(|word| capitalize(word)));
+    identifier(name)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Identifiers cannot start with a digit
+fn identifier(name: String) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Description lines for doc comments: the text and description of an
+/// endpoint, or of a parameter, without blank lines
+pub fn doc_lines(text: &str, description: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text.lines().map(str::trim).map(str::to_string).collect();
+    if !description.trim().is_empty() && description.trim() != text.trim() {
+        lines.extend(description.lines().map(str::trim).map(str::to_string));
+    }
+    lines.retain(|line| !line.is_empty());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn names_are_unique_identifiers() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: User Service
+    endpoints:
+      - text: Get user
+        verb: get
+        path: users/{id}
+        parameters:
+          - name: id
+            required: false
+          - name: idEvenement
+            required: true
+      - text: get_user
+        verb: POST
+        path: /users
+        parameters:
+          - name: 3d-model
+            required: false
+"#,
+        )
+        .unwrap();
+        let groups = client_groups(&catalog);
+        assert_eq!(pascal_case(&groups[0].words), "UserService");
+
+        let operations = &groups[0].operations;
+        assert_eq!(snake_case(&operations[0].words), "get_user");
+        assert_eq!(camel_case(&operations[1].words), "getUser2");
+        assert_eq!(operations[0].path, "/users/{id}");
+
+        let fields = &operations[0].fields;
+        assert_eq!(
+            (fields[0].location, fields[0].required),
+            (Location::Path, true)
+        );
+        assert_eq!(snake_case(&fields[1].words), "id_evenement");
+        assert_eq!(fields[1].location, Location::Query);
+        let field = &operations[1].fields[0];
+        assert_eq!(
+            (snake_case(&field.words), field.location),
+            ("_3d_model".to_string(), Location::Body)
+        );
+    }
+
+    #[test]
+    fn fills_placeholders_from_their_fields() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Odd
+    base: https://api.example.com
+    endpoints:
+      - text: Get thing
+        verb: GET
+        path: '/a/{x{y}'
+"#,
+        )
+        .unwrap();
+        let groups = client_groups(&catalog);
+        let fields = &groups[0].operations[0].fields;
+        assert_eq!(
+            (fields[0].name.as_str(), fields[0].location),
+            ("y", Location::Path)
+        );
+        let code = rust::generate(&groups).unwrap();
+        assert!(code.contains("encode(&params.y)"));
+        let code = typescript::generate(&groups).unwrap();
+        assert!(code.contains("encodeURIComponent(params.y)"));
+
+        let mut groups = groups;
+        groups[0].operations[0].fields.clear();
+        assert!(rust::generate(&groups).is_err());
+        assert!(typescript::generate(&groups).is_err());
+    }
+
+    #[test]
+    fn zips_both_clients() {
+        let catalog =
+            ApiCatalog::from_yaml(&std::fs::read_to_string("samples/output.yaml").unwrap())
+                .unwrap();
+        let bytes = clients_zip(&catalog).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["rust/Cargo.toml", "rust/src/lib.rs", "typescript/client.ts"]
+        );
+
+        let mut lib = String::new();
+        archive
+            .by_name("rust/src/lib.rs")
+            .unwrap()
+            .read_to_string(&mut lib)
+            .unwrap();
+        assert!(lib.contains("pub struct UserServiceClient"));
+    }
+}
@@ -0,0 +1,355 @@
+use std::error::Error;
+
+use super::{
+    doc_lines, pascal_case, path_parts, placeholder_field, snake_case, ClientGroup, Field,
+    Location, Operation, PathPart,
+};
+
+/// Words Rust reserves, suffixed with `_` when a name matches one
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Methods every generated client has
+const CLIENT_METHODS: &[&str] = &["new", "with_base_url", "with_http_client"];
+
+/// Code shared by the generated clients
+const PRELUDE: &str = r#"//! API client generated from the formatted endpoint catalog.
+
+use std::fmt;
+
+/// Failure of a call: the request could not be sent, or the API answered
+/// with an error status
+#[derive(Debug)]
+pub enum Error {
+    Http(reqwest::Error),
+    Status {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "request failed: {}", e),
+            Error::Status { status, body } => write!(f, "HTTP {}: {}", status, body),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+/// Percent-encodes a path segment
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Sends a request and returns the response body of successful calls
+async fn send(request: reqwest::RequestBuilder) -> Result<String, Error> {
+    let response = request.send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(Error::Status { status, body })
+    }
+}
+"#;
+
+/// Manifest of the generated crate
+pub fn cargo_toml() -> String {
+    r#"[package]
+name = "api-client"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+reqwest = { version = "0.12", features = ["json"] }
+serde_json = "1"
+"#
+    .to_string()
+}
+
+/// `src/lib.rs` of a crate with one client struct per group, one async
+/// method per endpoint and one parameters struct per endpoint
+pub fn generate(groups: &[ClientGroup]) -> Result<String, Box<dyn Error>> {
+    let mut code = PRELUDE.to_string();
+    for group in groups {
+        let client = format!("{}Client", pascal_case(&group.words));
+        for operation in &group.operations {
+            code.push('\n');
+            code.push_str(&params_struct(&client, operation));
+        }
+        code.push('\n');
+        code.push_str(&client_struct(&client, group)?);
+    }
+    Ok(code)
+}
+
+fn params_struct(client: &str, operation: &Operation) -> String {
+    let mut code = format!(
+        "/// Parameters of `{}::{}`\n#[derive(Clone, Debug, Default)]\npub struct {} {{\n",
+        client,
+        method_name(operation),
+        params_name(operation)
+    );
+    for field in &operation.fields {
+        for line in doc_lines(&field.description, "") {
+            code.push_str(&format!("    /// {}\n", line));
+        }
+        let field_type = if field.required {
+            "String"
+        } else {
+            "Option<String>"
+        };
+        code.push_str(&format!("    pub {}: {},\n", field_name(field), field_type));
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn client_struct(client: &str, group: &ClientGroup) -> Result<String, Box<dyn Error>> {
+    let mut code = String::new();
+    for line in doc_lines(&group.words.join(" "), group.description) {
+        code.push_str(&format!("/// {}\n", line));
+    }
+    code.push_str(&format!(
+        "#[derive(Clone, Debug)]\npub struct {} {{\n    http: reqwest::Client,\n    base_url: String,\n}}\n\n",
+        client
+    ));
+    // Without a base in the catalog, the caller has to give one
+    if !group.base.is_empty() {
+        code.push_str(&format!(
+            "impl Default for {} {{\n    fn default() -> Self {{\n        Self::new()\n    }}\n}}\n\n",
+            client
+        ));
+    }
+
+    code.push_str(&format!("impl {} {{\n", client));
+    if !group.base.is_empty() {
+        code.push_str(&format!(
+            "    /// Client for `{}`\n    pub fn new() -> Self {{\n        Self::with_base_url({:?})\n    }}\n\n",
+            group.base, group.base
+        ));
+    }
+    code.push_str(&format!(
+        "    pub fn with_base_url(base_url: impl Into<String>) -> Self {{\n        {} {{\n            http: reqwest::Client::new(),\n            base_url: base_url.into().trim_end_matches('/').to_string(),\n        }}\n    }}\n\n",
+        client
+    ));
+    code.push_str(
+        "    /// Uses a configured reqwest client, e.g. with default headers for authentication\n    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {\n        self.http = http;\n        self\n    }\n",
+    );
+    for operation in &group.operations {
+        code.push('\n');
+        code.push_str(&method(operation)?);
+    }
+    code.push_str("}\n");
+    Ok(code)
+}
+
+fn method(operation: &Operation) -> Result<String, Box<dyn Error>> {
+    let endpoint = operation.endpoint;
+    let mut code = String::new();
+    for line in doc_lines(&endpoint.text, &endpoint.description) {
+        code.push_str(&format!("    /// {}\n", line));
+    }
+    code.push_str(&format!(
+        "    ///\n    /// `{} {}`\n",
+        operation.method, operation.path
+    ));
+    // Every method takes its parameters, even when there are none
+    let argument = if operation.fields.is_empty() {
+        "_params"
+    } else {
+        "params"
+    };
+    code.push_str(&format!(
+        "    pub async fn {}(&self, {}: &{}) -> Result<String, Error> {{\n",
+        method_name(operation),
+        argument,
+        params_name(operation)
+    ));
+
+    // The path as a format string, placeholders filled from the parameters
+    let mut template = String::new();
+    let mut arguments = Vec::new();
+    match operation.base {
+        Some(base) => template.push_str(&escape_braces(base.trim_end_matches('/'))),
+        None => {
+            template.push_str("{}");
+            arguments.push("self.base_url".to_string());
+        }
+    }
+    for part in path_parts(&operation.path) {
+        match part {
+            PathPart::Literal(text) => template.push_str(&escape_braces(text)),
+            PathPart::Placeholder(name) => {
+                template.push_str("{}");
+                let field = placeholder_field(operation, name)?;
+                arguments.push(format!("encode(&params.{})", field_name(field)));
+            }
+        }
+    }
+    code.push_str(&format!(
+        "        let url = format!({:?}, {});\n",
+        template,
+        arguments.join(", ")
+    ));
+
+    let query: Vec<&Field> = located(operation, Location::Query);
+    let body: Vec<&Field> = located(operation, Location::Body);
+    code.push_str(&format!(
+        "        let request = self.http.request(reqwest::Method::{}, url);\n",
+        operation.method
+    ));
+    if !query.is_empty() {
+        code.push_str("        let mut query: Vec<(&str, &str)> = Vec::new();\n");
+        for field in &query {
+            if field.required {
+                code.push_str(&format!(
+                    "        query.push(({:?}, &params.{}));\n",
+                    field.name,
+                    field_name(field)
+                ));
+            } else {
+                code.push_str(&format!(
+                    "        if let Some(value) = &params.{} {{\n            query.push(({:?}, value));\n        }}\n",
+                    field_name(field),
+                    field.name
+                ));
+            }
+        }
+        code.push_str("        let request = request.query(&query);\n");
+    }
+    if !body.is_empty() {
+        code.push_str("        let mut body = serde_json::Map::new();\n");
+        for field in &body {
+            if field.required {
+                code.push_str(&format!(
+                    "        body.insert({:?}.to_string(), params.{}.clone().into());\n",
+                    field.name,
+                    field_name(field)
+                ));
+            } else {
+                code.push_str(&format!(
+                    "        if let Some(value) = &params.{} {{\n            body.insert({:?}.to_string(), value.clone().into());\n        }}\n",
+                    field_name(field),
+                    field.name
+                ));
+            }
+        }
+        code.push_str("        let request = request.json(&body);\n");
+    }
+    code.push_str("        send(request).await\n    }\n");
+    Ok(code)
+}
+
+fn escape_braces(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+fn located<'a>(operation: &'a Operation, location: Location) -> Vec<&'a Field> {
+    operation
+        .fields
+        .iter()
+        .filter(|field| field.location == location)
+        .collect()
+}
+
+fn method_name(operation: &Operation) -> String {
+    let name = safe_name(snake_case(&operation.words));
+    if CLIENT_METHODS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+fn params_name(operation: &Operation) -> String {
+    format!("{}Params", pascal_case(&operation.words))
+}
+
+fn field_name(field: &Field) -> String {
+    safe_name(snake_case(&field.words))
+}
+
+fn safe_name(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::ApiCatalog;
+    use crate::exporters::clients::client_groups;
+
+    #[test]
+    fn generates_a_method_per_endpoint() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Users
+    description: User accounts
+    base: https://api.example.com/
+    endpoints:
+      - text: Get user
+        description: Fetch one user
+        verb: GET
+        path: /users/{id}
+        parameters:
+          - name: id
+            description: User identifier
+            required: true
+          - name: type
+            required: false
+      - text: New
+        verb: POST
+        path: /users
+        parameters:
+          - name: email
+            required: true
+"#,
+        )
+        .unwrap();
+        let code = generate(&client_groups(&catalog)).unwrap();
+
+        assert!(code.contains("pub struct GetUserParams {\n    /// User identifier\n    pub id: String,\n    pub type_: Option<String>,\n}"));
+        assert!(code.contains(
+            "pub async fn get_user(&self, params: &GetUserParams) -> Result<String, Error> {"
+        ));
+        assert!(code
+            .contains(r#"let url = format!("{}/users/{}", self.base_url, encode(&params.id));"#));
+        assert!(code.contains(
+            r#"if let Some(value) = &params.type_ {
+            query.push(("type", value));"#
+        ));
+        assert!(code.contains("pub async fn new_(&self, params: &NewParams)"));
+        assert!(code.contains(r#"body.insert("email".to_string(), params.email.clone().into());"#));
+        assert!(code.contains(r#"Self::with_base_url("https://api.example.com/")"#));
+    }
+}
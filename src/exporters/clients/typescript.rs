@@ -0,0 +1,293 @@
+use std::error::Error;
+
+use super::{
+    camel_case, doc_lines, pascal_case, path_parts, placeholder_field, ClientGroup, Field,
+    Location, Operation, PathPart,
+};
+
+/// Members every generated client has
+const CLIENT_MEMBERS: &[&str] = &["constructor", "send", "baseUrl", "options"];
+
+/// Code shared by the generated clients
+const PRELUDE: &str = r#"// API client generated from the formatted endpoint catalog.
+
+/** Error status returned by the API */
+export class ApiError extends Error {
+  constructor(
+    readonly status: number,
+    readonly body: string,
+  ) {
+    super(`HTTP ${status}: ${body}`);
+  }
+}
+
+export interface RequestOptions {
+  /** Headers sent with every request, e.g. for authentication */
+  headers?: Record<string, string>;
+  /** Replaces the global fetch */
+  fetch?: typeof fetch;
+}
+"#;
+
+/// `client.ts`, a module with one class per group, one method per endpoint
+/// and one parameters interface per endpoint
+pub fn generate(groups: &[ClientGroup]) -> Result<String, Box<dyn Error>> {
+    let mut code = PRELUDE.to_string();
+    for group in groups {
+        for operation in &group.operations {
+            code.push('\n');
+            code.push_str(&params_interface(operation));
+        }
+        code.push('\n');
+        code.push_str(&client_class(group)?);
+    }
+    Ok(code)
+}
+
+fn params_interface(operation: &Operation) -> String {
+    let mut code = format!("export interface {} {{\n", params_name(operation));
+    for field in &operation.fields {
+        code.push_str(&doc_comment(&doc_lines(&field.description, ""), "  "));
+        let optional = if field.required { "" } else { "?" };
+        code.push_str(&format!(
+            "  {}{}: string;\n",
+            property_name(&field.name),
+            optional
+        ));
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn client_class(group: &ClientGroup) -> Result<String, Box<dyn Error>> {
+    let mut code = doc_comment(&doc_lines(&group.words.join(" "), group.description), "");
+    // Without a base in the catalog, the caller has to give one
+    let base_url = if group.base.is_empty() {
+        String::new()
+    } else {
+        format!(" = {}", string_literal(group.base.trim_end_matches('/')))
+    };
+    code.push_str(&format!(
+        "export class {}Client {{\n  constructor(\n    private readonly baseUrl: string{},\n    private readonly options: RequestOptions = {{}},\n  ) {{}}\n",
+        pascal_case(&group.words),
+        base_url
+    ));
+    for operation in &group.operations {
+        code.push('\n');
+        code.push_str(&method(operation)?);
+    }
+    code.push_str(
+        r#"
+  private async send(method: string, url: URL, body?: Record<string, string>): Promise<string> {
+    const headers: Record<string, string> = { ...this.options.headers };
+    if (body !== undefined) {
+      headers["Content-Type"] = "application/json";
+    }
+    const response = await (this.options.fetch ?? fetch)(url, {
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    const text = await response.text();
+    if (!response.ok) {
+      throw new ApiError(response.status, text);
+    }
+    return text;
+  }
+}
+"#,
+    );
+    Ok(code)
+}
+
+fn method(operation: &Operation) -> Result<String, Box<dyn Error>> {
+    let endpoint = operation.endpoint;
+    let mut lines = doc_lines(&endpoint.text, &endpoint.description);
+    lines.push(format!("`{} {}`", operation.method, operation.path));
+    let mut code = doc_comment(&lines, "  ");
+    code.push_str(&format!(
+        "  async {}(params: {}): Promise<string> {{\n",
+        method_name(operation),
+        params_name(operation)
+    ));
+
+    // The URL as a template literal, placeholders filled from the parameters
+    let mut template = match operation.base {
+        Some(base) => escape_template(base.trim_end_matches('/')),
+        None => "${this.baseUrl.replace(/\\/+$/, \"\")}".to_string(),
+    };
+    for part in path_parts(&operation.path) {
+        match part {
+            PathPart::Literal(text) => template.push_str(&escape_template(text)),
+            PathPart::Placeholder(name) => template.push_str(&format!(
+                "${{encodeURIComponent(params{})}}",
+                property_access(&placeholder_field(operation, name)?.name)
+            )),
+        }
+    }
+    code.push_str(&format!("    const url = new URL(`{}`);\n", template));
+
+    for field in located(operation, Location::Query) {
+        let value = format!("params{}", property_access(&field.name));
+        let set = format!(
+            "url.searchParams.set({}, {});",
+            string_literal(&field.name),
+            value
+        );
+        if field.required {
+            code.push_str(&format!("    {}\n", set));
+        } else {
+            code.push_str(&format!(
+                "    if ({} !== undefined) {{\n      {}\n    }}\n",
+                value, set
+            ));
+        }
+    }
+
+    let body = located(operation, Location::Body);
+    if body.is_empty() {
+        code.push_str(&format!(
+            "    return this.send({}, url);\n  }}\n",
+            string_literal(&operation.method)
+        ));
+        return Ok(code);
+    }
+    code.push_str("    const body: Record<string, string> = {};\n");
+    for field in body {
+        let value = format!("params{}", property_access(&field.name));
+        let set = format!("body[{}] = {};", string_literal(&field.name), value);
+        if field.required {
+            code.push_str(&format!("    {}\n", set));
+        } else {
+            code.push_str(&format!(
+                "    if ({} !== undefined) {{\n      {}\n    }}\n",
+                value, set
+            ));
+        }
+    }
+    code.push_str(&format!(
+        "    return this.send({}, url, body);\n  }}\n",
+        string_literal(&operation.method)
+    ));
+    Ok(code)
+}
+
+fn located<'a>(operation: &'a Operation, location: Location) -> Vec<&'a Field> {
+    operation
+        .fields
+        .iter()
+        .filter(|field| field.location == location)
+        .collect()
+}
+
+fn method_name(operation: &Operation) -> String {
+    let name = camel_case(&operation.words);
+    if CLIENT_MEMBERS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+fn params_name(operation: &Operation) -> String {
+    format!("{}Params", pascal_case(&operation.words))
+}
+
+fn doc_comment(lines: &[String], indent: &str) -> String {
+    match lines {
+        [] => String::new(),
+        [line] => format!("{}/** {} */\n", indent, line.replace("*/", "*\\/")),
+        _ => {
+            let mut comment = format!("{}/**\n", indent);
+            for line in lines {
+                comment.push_str(&format!("{} * {}\n", indent, line.replace("*/", "*\\/")));
+            }
+            comment.push_str(&format!("{} */\n", indent));
+            comment
+        }
+    }
+}
+
+/// Parameter names are kept as sent to the API, quoted when they are not
+/// identifiers
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+fn property_access(name: &str) -> String {
+    if is_identifier(name) {
+        format!(".{}", name)
+    } else {
+        format!("[{}]", string_literal(name))
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.starts_with(|c: char| c.is_ascii_digit())
+        && !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn string_literal(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+fn escape_template(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::ApiCatalog;
+    use crate::exporters::clients::client_groups;
+
+    #[test]
+    fn generates_a_method_per_endpoint() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Documents
+    base: https://api.example.com/
+    endpoints:
+      - text: List documents
+        verb: GET
+        path: /events/{idEvenement}/documents
+        parameters:
+          - name: page-size
+            required: false
+      - text: Send
+        description: Send a document
+        verb: POST
+        path: /documents
+        base: https://upload.example.com
+        parameters:
+          - name: file
+            description: File to upload
+            required: true
+"#,
+        )
+        .unwrap();
+        let code = generate(&client_groups(&catalog)).unwrap();
+
+        assert!(code.contains("export interface ListDocumentsParams {\n  \"page-size\"?: string;\n  idEvenement: string;\n}"));
+        assert!(code.contains(
+            "constructor(\n    private readonly baseUrl: string = \"https://api.example.com\","
+        ));
+        assert!(code.contains(
+            "const url = new URL(`${this.baseUrl.replace(/\\/+$/, \"\")}/events/${encodeURIComponent(params.idEvenement)}/documents`);"
+        ));
+        assert!(code.contains("if (params[\"page-size\"] !== undefined) {\n      url.searchParams.set(\"page-size\", params[\"page-size\"]);"));
+        assert!(code.contains("async send_(params: SendParams): Promise<string> {"));
+        assert!(code.contains("const url = new URL(`https://upload.example.com/documents`);"));
+        assert!(code.contains("body[\"file\"] = params.file;"));
+    }
+}
@@ -0,0 +1,165 @@
+use serde_yaml::Value;
+use std::error::Error;
+
+use crate::catalog::ApiCatalog;
+
+pub mod clients;
+pub mod openapi;
+pub mod requests;
+pub mod tools;
+
+/// Values of the `format` query parameter
+const FORMATS: &[&str] = &[
+    "yaml",
+    "openapi",
+    "openapi-json",
+    "openai",
+    "anthropic",
+    "mcp",
+    "postman",
+    "http",
+];
+
+/// Alternative outputs of the formatter, built from the formatted catalog
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    OpenApiYaml,
+    OpenApiJson,
+    OpenAiFunctions,
+    AnthropicTools,
+    McpTools,
+    PostmanCollection,
+    HttpFile,
+}
+
+/// An exported document and how to send it
+pub struct Export {
+    pub content_type: &'static str,
+    pub filename: &'static str,
+    pub body: String,
+}
+
+impl ExportFormat {
+    /// Format asked for with `?format=`, else with the `Accept` header. None
+    /// for the catalog YAML, an error for an unknown `format` value.
+    pub fn from_request(
+        format: Option<&str>,
+        accept: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        if let Some(format) = format {
+            return match format.trim().to_lowercase().as_str() {
+                "" | "yaml" | "catalog" => Ok(None),
+                "openapi" | "openapi-yaml" => Ok(Some(ExportFormat::OpenApiYaml)),
+                "openapi-json" => Ok(Some(ExportFormat::OpenApiJson)),
+                "openai" => Ok(Some(ExportFormat::OpenAiFunctions)),
+                "anthropic" => Ok(Some(ExportFormat::AnthropicTools)),
+                "mcp" => Ok(Some(ExportFormat::McpTools)),
+                "postman" => Ok(Some(ExportFormat::PostmanCollection)),
+                "http" => Ok(Some(ExportFormat::HttpFile)),
+                other => Err(format!(
+                    "Unknown export format '{}', expected one of: {}",
+                    other,
+                    FORMATS.join(", ")
+                )),
+            };
+        }
+
+        let accept = accept.unwrap_or_default();
+        if accept.contains("application/vnd.oai.openapi+json") {
+            Ok(Some(ExportFormat::OpenApiJson))
+        } else if accept.contains("application/vnd.oai.openapi") {
+            Ok(Some(ExportFormat::OpenApiYaml))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn export(self, catalog: &ApiCatalog) -> Result<Export, Box<dyn Error>> {
+        match self {
+            ExportFormat::OpenApiYaml => Ok(Export {
+                content_type: "application/vnd.oai.openapi",
+                filename: "openapi.yaml",
+                body: serde_yaml::to_string(&openapi::to_openapi(catalog))?,
+            }),
+            ExportFormat::OpenApiJson => Ok(Export {
+                content_type: "application/vnd.oai.openapi+json",
+                filename: "openapi.json",
+                body: serde_json::to_string_pretty(&openapi::to_openapi(catalog))?,
+            }),
+            ExportFormat::OpenAiFunctions => Ok(Export {
+                content_type: "application/json",
+                filename: "openai_functions.json",
+                body: serde_json::to_string_pretty(&tools::openai_functions(catalog))?,
+            }),
+            ExportFormat::AnthropicTools => Ok(Export {
+                content_type: "application/json",
+                filename: "anthropic_tools.json",
+                body: serde_json::to_string_pretty(&tools::anthropic_tools(catalog))?,
+            }),
+            ExportFormat::McpTools => Ok(Export {
+                content_type: "application/json",
+                filename: "mcp_tools.json",
+                body: serde_json::to_string_pretty(&tools::mcp_tools(catalog))?,
+            }),
+            ExportFormat::PostmanCollection => Ok(Export {
+                content_type: "application/json",
+                filename: "postman_collection.json",
+                body: serde_json::to_string_pretty(&requests::postman_collection(catalog))?,
+            }),
+            ExportFormat::HttpFile => Ok(Export {
+                content_type: "text/plain",
+                filename: "requests.http",
+                body: requests::http_file(catalog),
+            }),
+        }
+    }
+}
+
+/// Methods whose non-path parameters go in a JSON body, the query string
+/// carrying them for the other methods
+const BODY_METHODS: &[&str] = &["POST", "PUT", "PATCH"];
+
+/// True when the non-path parameters of a `verb` call go in its body
+pub fn has_body(verb: &str) -> bool {
+    BODY_METHODS
+        .iter()
+        .any(|method| method.eq_ignore_ascii_case(verb.trim()))
+}
+
+/// A mapping built from fields in insertion order, which serde_yaml keeps
+/// for both YAML and JSON output
+pub fn mapping<const N: usize>(fields: [(&str, Value); N]) -> Value {
+    Value::Mapping(
+        fields
+            .into_iter()
+            .map(|(key, value)| (Value::from(key), value))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_the_format_from_the_query_then_the_accept_header() {
+        assert_eq!(
+            ExportFormat::from_request(Some("openapi"), Some("application/json")),
+            Ok(Some(ExportFormat::OpenApiYaml))
+        );
+        assert_eq!(
+            ExportFormat::from_request(None, Some("application/vnd.oai.openapi+json")),
+            Ok(Some(ExportFormat::OpenApiJson))
+        );
+        assert_eq!(ExportFormat::from_request(None, Some("*/*")), Ok(None));
+        assert_eq!(
+            ExportFormat::from_request(Some("MCP"), None),
+            Ok(Some(ExportFormat::McpTools))
+        );
+        assert_eq!(
+            ExportFormat::from_request(Some("http"), Some("application/vnd.oai.openapi")),
+            Ok(Some(ExportFormat::HttpFile))
+        );
+        assert!(ExportFormat::from_request(Some("wsdl"), None).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use serde_yaml::Value;
+
+use super::{has_body, mapping};
+use crate::catalog::{ApiCatalog, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+/// Methods OpenAPI path items can hold
+const OPERATION_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Builds an OpenAPI 3.1 document: groups become tags, the origins of the
+/// bases become servers and their paths prefix the endpoint paths, path
+/// placeholders become path parameters and the other parameters go in the
+/// query string, or in a JSON request body for POST, PUT and PATCH.
+/// Endpoints with a verb OpenAPI has no operation for are left out.
+pub fn to_openapi(catalog: &ApiCatalog) -> Value {
+    // Every distinct origin, the first one being the default server
+    let mut servers: Vec<&str> = Vec::new();
+    for base in catalog.api_groups.iter().flat_map(|group| {
+        std::iter::once(group.base.as_str()).chain(
+            group
+                .endpoints
+                .iter()
+                .filter_map(|endpoint| endpoint.base.as_deref()),
+        )
+    }) {
+        let (origin, _) = split_base(base);
+        if !origin.is_empty() && !servers.contains(&origin) {
+            servers.push(origin);
+        }
+    }
+
+    let title = match catalog.api_groups.as_slice() {
+        [group] => group.name.clone(),
+        _ => "API catalog".to_string(),
+    };
+
+    let mut paths = serde_yaml::Mapping::new();
+    let mut operation_ids: Vec<String> = Vec::new();
+    for group in &catalog.api_groups {
+        for endpoint in &group.endpoints {
+            let method = endpoint.verb.trim().to_lowercase();
+            if !OPERATION_METHODS.contains(&method.as_str()) {
+                continue;
+            }
+            let (origin, prefix) = split_base(endpoint.base.as_deref().unwrap_or(&group.base));
+            let path = match endpoint.path.trim().trim_start_matches('/') {
+                "" if !prefix.is_empty() => prefix.to_string(),
+                path => format!("{}/{}", prefix, path),
+            };
+            let path_item = paths
+                .entry(Value::from(path.as_str()))
+                .or_insert_with(|| Value::Mapping(Default::default()));
+            let Value::Mapping(path_item) = path_item else {
+                continue;
+            };
+            // Duplicated verb and path, the first endpoint wins
+            if path_item.contains_key(method.as_str()) {
+                continue;
+            }
+
+            let operation_id = unique_operation_id(&endpoint.text, &mut operation_ids);
+            path_item.insert(
+                Value::from(method.as_str()),
+                operation(
+                    endpoint,
+                    &method,
+                    &path,
+                    &group.name,
+                    origin,
+                    servers.first().copied(),
+                    operation_id,
+                ),
+            );
+        }
+    }
+
+    let mut document = serde_yaml::Mapping::new();
+    document.insert("openapi".into(), "3.1.0".into());
+    document.insert(
+        "info".into(),
+        mapping([("title", title.into()), ("version", "1.0.0".into())]),
+    );
+    if !servers.is_empty() {
+        document.insert(
+            "servers".into(),
+            Value::Sequence(
+                servers
+                    .iter()
+                    .map(|url| mapping([("url", (*url).into())]))
+                    .collect(),
+            ),
+        );
+    }
+    document.insert(
+        "tags".into(),
+        Value::Sequence(
+            catalog
+                .api_groups
+                .iter()
+                .map(|group| {
+                    let mut tag = serde_yaml::Mapping::new();
+                    tag.insert("name".into(), group.name.as_str().into());
+                    if !group.description.is_empty() {
+                        tag.insert("description".into(), group.description.as_str().into());
+                    }
+                    Value::Mapping(tag)
+                })
+                .collect(),
+        ),
+    );
+    document.insert("paths".into(), Value::Mapping(paths));
+    Value::Mapping(document)
+}
+
+fn operation(
+    endpoint: &Endpoint,
+    method: &str,
+    path: &str,
+    tag: &str,
+    origin: &str,
+    default_server: Option<&str>,
+    operation_id: String,
+) -> Value {
+    let mut operation = serde_yaml::Mapping::new();
+    operation.insert("tags".into(), Value::Sequence(vec![tag.into()]));
+    operation.insert("summary".into(), endpoint.text.as_str().into());
+    if !endpoint.description.is_empty() {
+        operation.insert("description".into(), endpoint.description.as_str().into());
+    }
+    operation.insert("operationId".into(), operation_id.into());
+    // Operations served elsewhere than the first server override it
+    if !origin.is_empty() && Some(origin) != default_server {
+        operation.insert(
+            "servers".into(),
+            Value::Sequence(vec![mapping([("url", origin.into())])]),
+        );
+    }
+
+    let placeholders = path_placeholders(path);
+    let in_body = has_body(method);
+    let mut parameters = Vec::new();
+    let mut body_fields: Vec<&Parameter> = Vec::new();
+    for parameter in &endpoint.parameters {
+        let in_path = placeholders.contains(&parameter.name.as_str());
+        if !in_path && in_body {
+            body_fields.push(parameter);
+            continue;
+        }
+        let mut value = serde_yaml::Mapping::new();
+        value.insert("name".into(), parameter.name.as_str().into());
+        value.insert("in".into(), if in_path { "path" } else { "query" }.into());
+        if !parameter.description.is_empty() {
+            value.insert("description".into(), parameter.description.as_str().into());
+        }
+        // Path parameters must be required
+        value.insert("required".into(), (in_path || parameter.required).into());
+        value.insert("schema".into(), mapping([("type", "string".into())]));
+        if !parameter.alternatives.is_empty() {
+            value.insert("x-alternatives".into(), alternatives(parameter));
+        }
+        parameters.push(Value::Mapping(value));
+    }
+    // Placeholders the catalog has no parameter for are still declared
+    for placeholder in placeholders {
+        if !endpoint
+            .parameters
+            .iter()
+            .any(|parameter| parameter.name == placeholder)
+        {
+            parameters.push(mapping([
+                ("name", placeholder.into()),
+                ("in", "path".into()),
+                ("required", true.into()),
+                ("schema", mapping([("type", "string".into())])),
+            ]));
+        }
+    }
+    if !parameters.is_empty() {
+        operation.insert("parameters".into(), Value::Sequence(parameters));
+    }
+
+    if !body_fields.is_empty() {
+        let mut properties = serde_yaml::Mapping::new();
+        for parameter in &body_fields {
+            let mut property = serde_yaml::Mapping::new();
+            property.insert("type".into(), "string".into());
+            if !parameter.description.is_empty() {
+                property.insert("description".into(), parameter.description.as_str().into());
+            }
+            if !parameter.alternatives.is_empty() {
+                property.insert("x-alternatives".into(), alternatives(parameter));
+            }
+            properties.insert(parameter.name.as_str().into(), Value::Mapping(property));
+        }
+        let mut schema = serde_yaml::Mapping::new();
+        schema.insert("type".into(), "object".into());
+        schema.insert("properties".into(), Value::Mapping(properties));
+        let required: Vec<Value> = body_fields
+            .iter()
+            .filter(|parameter| parameter.required)
+            .map(|parameter| parameter.name.as_str().into())
+            .collect();
+        let any_required = !required.is_empty();
+        if any_required {
+            schema.insert("required".into(), Value::Sequence(required));
+        }
+        operation.insert(
+            "requestBody".into(),
+            mapping([
+                ("required", any_required.into()),
+                (
+                    "content",
+                    mapping([(
+                        "application/json",
+                        mapping([("schema", Value::Mapping(schema))]),
+                    )]),
+                ),
+            ]),
+        );
+    }
+
+    operation.insert(
+        "responses".into(),
+        mapping([(
+            "200",
+            mapping([("description", "Successful response".into())]),
+        )]),
+    );
+    if !endpoint.suggested_sentence.is_empty() {
+        operation.insert(
+            "x-suggested-sentence".into(),
+            endpoint.suggested_sentence.as_str().into(),
+        );
+    }
+    Value::Mapping(operation)
+}
+
+/// `https://api.example.com/v1/` is served by `https://api.example.com`
+/// under the `/v1` prefix
+pub fn split_base(base: &str) -> (&str, &str) {
+    let base = base.trim().trim_end_matches('/');
+    let after_scheme = base.find("://").map_or(0, |index| index + 3);
+    match base[after_scheme..].find('/') {
+        Some(index) => base.split_at(after_scheme + index),
+        None => (base, ""),
+    }
+}
+
+fn alternatives(parameter: &Parameter) -> Value {
+    Value::Sequence(
+        parameter
+            .alternatives
+            .iter()
+            .map(|alternative| alternative.as_str().into())
+            .collect(),
+    )
+}
+
+/// camelCase of the endpoint text, numbered when already taken
+fn unique_operation_id(text: &str, taken: &mut Vec<String>) -> String {
+    let mut base = String::new();
+    for (index, word) in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .enumerate()
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if index == 0 {
+                base.extend(first.to_lowercase());
+            } else {
+                base.extend(first.to_uppercase());
+            }
+            base.push_str(chars.as_str());
+        }
+    }
+    if base.is_empty() {
+        base = "operation".to_string();
+    }
+
+    let mut operation_id = base.clone();
+    let mut count = 1;
+    while taken.contains(&operation_id) {
+        count += 1;
+        operation_id = format!("{}{}", base, count);
+    }
+    taken.push(operation_id.clone());
+    operation_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers;
+
+    #[test]
+    fn exports_the_formatted_sample() {
+        let catalog =
+            ApiCatalog::from_yaml(&std::fs::read_to_string("samples/output.yaml").unwrap())
+                .unwrap();
+        let document = to_openapi(&catalog);
+
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(
+            document["tags"].as_sequence().unwrap().len(),
+            catalog.api_groups.len()
+        );
+        let operations: usize = document["paths"]
+            .as_mapping()
+            .unwrap()
+            .values()
+            .map(|path_item| path_item.as_mapping().unwrap().len())
+            .sum();
+        let endpoints: usize = catalog
+            .api_groups
+            .iter()
+            .map(|group| group.endpoints.len())
+            .sum();
+        assert_eq!(operations, endpoints);
+    }
+
+    #[test]
+    fn places_parameters_by_verb_and_placeholders() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Users
+    description: User accounts
+    base: https://api.example.com
+    endpoints:
+      - text: Get user
+        description: Fetch one user
+        suggested_sentence: Show user 42
+        verb: GET
+        path: /users/{id}
+        parameters:
+          - name: id
+            required: true
+            alternatives: [user_id]
+          - name: expand
+            required: false
+      - text: Update user
+        verb: PATCH
+        path: /users/{id}
+        parameters:
+          - name: id
+            required: true
+          - name: email
+            required: true
+          - name: name
+            required: false
+  - name: Files
+    base: https://files.example.com/v2/
+    endpoints:
+      - text: Get user
+        verb: GET
+        path: /files
+"#,
+        )
+        .unwrap();
+        let document = to_openapi(&catalog);
+
+        assert_eq!(document["servers"][0]["url"], "https://api.example.com");
+        assert_eq!(document["servers"][1]["url"], "https://files.example.com");
+        assert_eq!(document["tags"][0]["description"], "User accounts");
+
+        let get = &document["paths"]["/users/{id}"]["get"];
+        assert_eq!(get["operationId"], "getUser");
+        assert_eq!(get["x-suggested-sentence"], "Show user 42");
+        assert_eq!(get["parameters"][0]["in"], "path");
+        assert_eq!(get["parameters"][0]["x-alternatives"][0], "user_id");
+        assert_eq!(get["parameters"][1]["in"], "query");
+        assert_eq!(get["parameters"][1]["required"], false);
+        assert!(get.get("servers").is_none());
+
+        let patch = &document["paths"]["/users/{id}"]["patch"];
+        assert_eq!(patch["parameters"].as_sequence().unwrap().len(), 1);
+        let schema = &patch["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["required"], Value::Sequence(vec!["email".into()]));
+        assert!(schema["properties"].get("name").is_some());
+
+        let files = &document["paths"]["/v2/files"]["get"];
+        assert_eq!(files["operationId"], "getUser2");
+        assert_eq!(files["servers"][0]["url"], "https://files.example.com");
+    }
+
+    #[test]
+    fn round_trips_through_the_openapi_import() {
+        let catalog =
+            ApiCatalog::from_yaml(&std::fs::read_to_string("samples/output.yaml").unwrap())
+                .unwrap();
+        let document = to_openapi(&catalog);
+        let imported = importers::openapi::import(&document).unwrap();
+        assert_eq!(imported.api_groups.len(), catalog.api_groups.len());
+        assert_eq!(
+            imported.api_groups[0].endpoints[0].path,
+            catalog.api_groups[0].endpoints[0].path
+        );
+    }
+}
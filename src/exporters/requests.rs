@@ -0,0 +1,535 @@
+use serde_yaml::{Mapping, Value};
+
+use super::clients::{path_parts, PathPart};
+use super::openapi::split_base;
+use super::{has_body, mapping};
+use crate::catalog::{ApiCatalog, Endpoint};
+use crate::catalog_validator::path_placeholders;
+use crate::sentence_coverage::{sentence_value, words};
+
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Variable standing for the base URL of groups the catalog gives none
+const BASE_URL_VARIABLE: &str = "baseUrl";
+const DEFAULT_BASE_URL: &str = "http://localhost";
+
+/// A group of the catalog with its endpoints as example requests
+pub struct ExampleGroup<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub requests: Vec<ExampleRequest<'a>>,
+}
+
+/// An endpoint with example values for its parameters, ready to send
+pub struct ExampleRequest<'a> {
+    pub name: &'a str,
+    /// The suggested sentence, or the endpoint description when it has none
+    pub description: &'a str,
+    pub method: String,
+    /// Base URL of the endpoint, its own or its group's, empty when unknown
+    pub base: &'a str,
+    pub path: &'a str,
+    pub path_values: Vec<Example<'a>>,
+    pub query: Vec<Example<'a>>,
+    pub body: Vec<Example<'a>>,
+}
+
+pub struct Example<'a> {
+    pub name: &'a str,
+    pub value: String,
+    pub description: &'a str,
+    /// False for optional parameters the suggested sentence gives no value for
+    pub enabled: bool,
+}
+
+/// One example request per endpoint, grouped as in the catalog
+pub fn example_groups(catalog: &ApiCatalog) -> Vec<ExampleGroup<'_>> {
+    catalog
+        .api_groups
+        .iter()
+        .map(|group| ExampleGroup {
+            name: group.name.trim(),
+            description: group.description.trim(),
+            requests: group
+                .endpoints
+                .iter()
+                .map(|endpoint| {
+                    example_request(endpoint, endpoint.base.as_deref().unwrap_or(&group.base))
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn example_request<'a>(endpoint: &'a Endpoint, base: &'a str) -> ExampleRequest<'a> {
+    let sentence = endpoint.suggested_sentence.trim();
+    let method = endpoint.verb.trim().to_uppercase();
+    let in_body = has_body(&method);
+    let placeholders = path_placeholders(&endpoint.path);
+
+    // Placeholders are filled even when they have no parameter
+    let path_values = placeholders
+        .iter()
+        .map(|placeholder| {
+            let parameter = endpoint
+                .parameters
+                .iter()
+                .find(|parameter| parameter.name.trim() == *placeholder);
+            let mut names = vec![*placeholder];
+            names.extend(
+                parameter
+                    .iter()
+                    .flat_map(|parameter| parameter.alternatives.iter().map(String::as_str)),
+            );
+            Example {
+                name: placeholder,
+                value: sentence_value(sentence, &names).unwrap_or_else(|| guess(placeholder)),
+                description: parameter.map_or("", |parameter| parameter.description.trim()),
+                enabled: true,
+            }
+        })
+        .collect();
+
+    let mut query = Vec::new();
+    let mut body = Vec::new();
+    for parameter in &endpoint.parameters {
+        let name = parameter.name.trim();
+        if name.is_empty() || placeholders.contains(&name) {
+            continue;
+        }
+        let mut names = vec![name];
+        names.extend(parameter.alternatives.iter().map(String::as_str));
+        let value = sentence_value(sentence, &names);
+        let example = Example {
+            name,
+            enabled: parameter.required || value.is_some(),
+            value: value.unwrap_or_else(|| guess(name)),
+            description: parameter.description.trim(),
+        };
+        if in_body {
+            body.push(example);
+        } else {
+            query.push(example);
+        }
+    }
+
+    ExampleRequest {
+        name: endpoint.text.trim(),
+        description: if sentence.is_empty() {
+            endpoint.description.trim()
+        } else {
+            sentence
+        },
+        method,
+        base: base.trim(),
+        path: endpoint.path.trim(),
+        path_values,
+        query,
+        body,
+    }
+}
+
+/// A Postman v2.1 collection: groups become folders, `{placeholders}`
+/// become `:variables` holding their example value, and optional query
+/// parameters without a value in the sentence are added disabled
+pub fn postman_collection(catalog: &ApiCatalog) -> Value {
+    let groups = example_groups(catalog);
+
+    let mut collection = Mapping::new();
+    collection.insert(
+        "info".into(),
+        mapping([
+            ("name", "API catalog".into()),
+            ("schema", POSTMAN_SCHEMA.into()),
+        ]),
+    );
+    collection.insert(
+        "item".into(),
+        Value::Sequence(groups.iter().map(postman_folder).collect()),
+    );
+    if needs_base_url(&groups) {
+        collection.insert(
+            "variable".into(),
+            Value::Sequence(vec![mapping([
+                ("key", BASE_URL_VARIABLE.into()),
+                ("value", DEFAULT_BASE_URL.into()),
+            ])]),
+        );
+    }
+    Value::Mapping(collection)
+}
+
+fn postman_folder(group: &ExampleGroup) -> Value {
+    mapping([
+        ("name", group.name.into()),
+        ("description", group.description.into()),
+        (
+            "item",
+            Value::Sequence(group.requests.iter().map(postman_item).collect()),
+        ),
+    ])
+}
+
+fn postman_item(request: &ExampleRequest) -> Value {
+    let mut fields = Mapping::new();
+    fields.insert("method".into(), request.method.as_str().into());
+
+    let body: Vec<&Example> = request
+        .body
+        .iter()
+        .filter(|example| example.enabled)
+        .collect();
+    let mut headers = Vec::new();
+    if !body.is_empty() {
+        headers.push(mapping([
+            ("key", "Content-Type".into()),
+            ("value", "application/json".into()),
+        ]));
+    }
+    fields.insert("header".into(), Value::Sequence(headers));
+    fields.insert("description".into(), request.description.into());
+    fields.insert("url".into(), postman_url(request));
+    if !body.is_empty() {
+        fields.insert(
+            "body".into(),
+            mapping([
+                ("mode", "raw".into()),
+                ("raw", json_body(&body).into()),
+                (
+                    "options",
+                    mapping([("raw", mapping([("language", "json".into())]))]),
+                ),
+            ]),
+        );
+    }
+
+    mapping([
+        ("name", request.name.into()),
+        ("request", Value::Mapping(fields)),
+    ])
+}
+
+fn postman_url(request: &ExampleRequest) -> Value {
+    let (host, prefix) = if request.base.is_empty() {
+        (format!("{{{{{}}}}}", BASE_URL_VARIABLE), "")
+    } else {
+        let (origin, prefix) = split_base(request.base);
+        (origin.to_string(), prefix)
+    };
+
+    // A placeholder making up a whole segment becomes a Postman path
+    // variable, one within a segment is replaced by its value
+    let path: Vec<String> = prefix
+        .split('/')
+        .chain(request.path.split('/'))
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match path_parts(segment).as_slice() {
+            [PathPart::Placeholder(name)] => format!(":{}", name),
+            _ => fill_placeholders(segment, request, false),
+        })
+        .collect();
+
+    let enabled: Vec<String> = request
+        .query
+        .iter()
+        .filter(|example| example.enabled)
+        .map(|example| format!("{}={}", example.name, example.value))
+        .collect();
+    let mut raw = format!("{}/{}", host, path.join("/"));
+    if !enabled.is_empty() {
+        raw = format!("{}?{}", raw, enabled.join("&"));
+    }
+
+    let mut url = Mapping::new();
+    url.insert("raw".into(), raw.into());
+    url.insert("host".into(), Value::Sequence(vec![host.into()]));
+    url.insert(
+        "path".into(),
+        Value::Sequence(path.into_iter().map(Value::from).collect()),
+    );
+    if !request.query.is_empty() {
+        let query = request
+            .query
+            .iter()
+            .map(|example| {
+                let mut entry = postman_entry(example);
+                if !example.enabled {
+                    if let Value::Mapping(fields) = &mut entry {
+                        fields.insert("disabled".into(), true.into());
+                    }
+                }
+                entry
+            })
+            .collect();
+        url.insert("query".into(), Value::Sequence(query));
+    }
+    if !request.path_values.is_empty() {
+        url.insert(
+            "variable".into(),
+            Value::Sequence(request.path_values.iter().map(postman_entry).collect()),
+        );
+    }
+    Value::Mapping(url)
+}
+
+fn postman_entry(example: &Example) -> Value {
+    mapping([
+        ("key", example.name.into()),
+        ("value", example.value.as_str().into()),
+        ("description", example.description.into()),
+    ])
+}
+
+/// A `.http` request file, as run by the VS Code REST Client and JetBrains
+/// HTTP Client: one `###` block per endpoint with the example values in the
+/// URL and JSON body
+pub fn http_file(catalog: &ApiCatalog) -> String {
+    let groups = example_groups(catalog);
+    let mut file = String::new();
+    if needs_base_url(&groups) {
+        file.push_str(&format!(
+            "@{} = {}\n\n",
+            BASE_URL_VARIABLE, DEFAULT_BASE_URL
+        ));
+    }
+
+    for request in groups.iter().flat_map(|group| &group.requests) {
+        file.push_str(&format!("### {}\n", request.name));
+        for line in request.description.lines().map(str::trim) {
+            if !line.is_empty() {
+                file.push_str(&format!("# {}\n", line));
+            }
+        }
+
+        let base = if request.base.is_empty() {
+            format!("{{{{{}}}}}", BASE_URL_VARIABLE)
+        } else {
+            request.base.trim_end_matches('/').to_string()
+        };
+        let mut url = format!(
+            "{}/{}",
+            base,
+            fill_placeholders(request.path, request, true).trim_start_matches('/')
+        );
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        for example in request.query.iter().filter(|example| example.enabled) {
+            query.append_pair(example.name, &example.value);
+        }
+        let query = query.finish();
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query);
+        }
+        file.push_str(&format!("{} {}\n", request.method, url));
+
+        let body: Vec<&Example> = request
+            .body
+            .iter()
+            .filter(|example| example.enabled)
+            .collect();
+        if !body.is_empty() {
+            file.push_str(&format!(
+                "Content-Type: application/json\n\n{}\n",
+                json_body(&body)
+            ));
+        }
+        file.push('\n');
+    }
+    file
+}
+
+fn needs_base_url(groups: &[ExampleGroup]) -> bool {
+    groups
+        .iter()
+        .flat_map(|group| &group.requests)
+        .any(|request| request.base.is_empty())
+}
+
+/// The placeholders of a path replaced by their example values
+fn fill_placeholders(path: &str, request: &ExampleRequest, encode: bool) -> String {
+    path_parts(path)
+        .into_iter()
+        .map(|part| match part {
+            PathPart::Literal(text) => text.to_string(),
+            PathPart::Placeholder(name) => {
+                let value = request
+                    .path_values
+                    .iter()
+                    .find(|example| example.name == name)
+                    .map_or(name, |example| example.value.as_str());
+                if encode {
+                    url::form_urlencoded::byte_serialize(value.as_bytes())
+                        .collect::<String>()
+                        .replace('+', "%20")
+                } else {
+                    value.to_string()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Fields in parameter order, which a serde_yaml mapping keeps
+fn json_body(body: &[&Example]) -> String {
+    let fields: Mapping = body
+        .iter()
+        .map(|example| (example.name.into(), example.value.as_str().into()))
+        .collect();
+    serde_json::to_string_pretty(&fields).unwrap_or_default()
+}
+
+/// A plausible value for a parameter the suggested sentence gives none for
+fn guess(name: &str) -> String {
+    let name_words = words(name);
+    let has = |word: &str| name_words.iter().any(|name_word| name_word == word);
+    let value = if has("email") || has("mail") {
+        "user@example.com"
+    } else if has("date") || has("day") {
+        "2024-01-01"
+    } else if has("url") || has("uri") || has("link") {
+        "https://example.com"
+    } else if has("limit") || has("size") || has("count") {
+        "10"
+    } else if has("id") || has("page") || has("number") {
+        "1"
+    } else {
+        "example"
+    };
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+api_groups:
+  - name: Users
+    description: User accounts
+    base: https://api.example.com/v1
+    endpoints:
+      - text: Get user
+        suggested_sentence: Show the orders of user id 42
+        verb: GET
+        path: /users/{userId}
+        parameters:
+          - name: userId
+            required: true
+            alternatives: [user_id]
+          - name: expand
+            description: Relations to include
+            required: false
+          - name: page
+            required: false
+      - text: Create user
+        description: Register a user account
+        verb: POST
+        path: /users
+        parameters:
+          - name: email
+            required: true
+          - name: alias
+            required: true
+          - name: nickname
+            required: false
+  - name: Search
+    endpoints:
+      - text: Search
+        suggested_sentence: Search for "rust books", expand orders
+        verb: GET
+        path: /search
+        parameters:
+          - name: q
+            required: false
+            alternatives: [for]
+          - name: expand
+            required: false
+"#;
+
+    #[test]
+    fn takes_example_values_from_the_suggested_sentence() {
+        let catalog = ApiCatalog::from_yaml(CATALOG).unwrap();
+        let groups = example_groups(&catalog);
+
+        let get_user = &groups[0].requests[0];
+        assert_eq!(get_user.description, "Show the orders of user id 42");
+        assert_eq!(get_user.path_values[0].value, "42");
+        assert_eq!(
+            (get_user.query[0].value.as_str(), get_user.query[0].enabled),
+            ("example", false)
+        );
+
+        let create_user = &groups[0].requests[1];
+        assert_eq!(create_user.description, "Register a user account");
+        assert_eq!(create_user.body[0].value, "user@example.com");
+        assert!(!create_user.body[2].enabled);
+
+        let search = &groups[1].requests[0];
+        assert_eq!(search.query[0].value, "rust books");
+        assert_eq!(
+            (search.query[1].value.as_str(), search.query[1].enabled),
+            ("orders", true)
+        );
+    }
+
+    #[test]
+    fn exports_a_postman_collection_the_importer_reads_back() {
+        let catalog = ApiCatalog::from_yaml(CATALOG).unwrap();
+        let collection = postman_collection(&catalog);
+
+        assert_eq!(collection["info"]["schema"], POSTMAN_SCHEMA);
+        let url = &collection["item"][0]["item"][0]["request"]["url"];
+        assert_eq!(url["raw"], "https://api.example.com/v1/users/:userId");
+        assert_eq!(url["variable"][0]["value"], "42");
+        assert_eq!(url["query"][0]["disabled"], true);
+        assert_eq!(
+            collection["item"][0]["item"][1]["request"]["body"]["raw"],
+            "{\n  \"email\": \"user@example.com\",\n  \"alias\": \"example\"\n}"
+        );
+        assert_eq!(collection["variable"][0]["key"], "baseUrl");
+
+        let imported = crate::importers::postman::import(&collection).unwrap();
+        let endpoint = &imported.api_groups[0].endpoints[0];
+        assert_eq!(imported.api_groups[0].name, "Users");
+        assert_eq!(endpoint.path, "/v1/users/{userId}");
+        assert_eq!(endpoint.description, "Show the orders of user id 42");
+        assert_eq!(
+            imported.api_groups[0].endpoints[1].parameters[0].name,
+            "email"
+        );
+    }
+
+    #[test]
+    fn exports_an_http_file() {
+        let catalog = ApiCatalog::from_yaml(CATALOG).unwrap();
+        let file = http_file(&catalog);
+
+        assert!(file.starts_with("@baseUrl = http://localhost\n\n### Get user\n"));
+        assert!(file.contains(
+            "# Show the orders of user id 42\nGET https://api.example.com/v1/users/42\n\n"
+        ));
+        assert!(file.contains(
+            "### Create user\n# Register a user account\nPOST https://api.example.com/v1/users\nContent-Type: application/json\n\n{\n  \"email\": \"user@example.com\",\n  \"alias\": \"example\"\n}\n"
+        ));
+        assert!(file.contains("GET {{baseUrl}}/search?q=rust+books&expand=orders\n"));
+    }
+
+    #[test]
+    fn fills_placeholders_the_path_parts_find() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Odd
+    base: https://api.example.com
+    endpoints:
+      - text: Get thing
+        suggested_sentence: Get the thing with y 5
+        verb: GET
+        path: '/a/{x{y}'
+"#,
+        )
+        .unwrap();
+        let groups = example_groups(&catalog);
+        assert_eq!(groups[0].requests[0].path_values[0].name, "y");
+        assert!(http_file(&catalog).contains("GET https://api.example.com/a/{x5\n"));
+    }
+}
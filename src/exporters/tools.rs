@@ -0,0 +1,287 @@
+use serde_yaml::Value;
+
+use super::mapping;
+use crate::catalog::{ApiCatalog, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+/// Longest tool name OpenAI and Anthropic accept
+const MAX_NAME_LENGTH: usize = 64;
+
+/// An endpoint described for function calling
+pub struct Tool<'a> {
+    /// snake_case of the endpoint text, unique across the catalog
+    pub name: String,
+    pub description: String,
+    /// JSON Schema object of the parameters
+    pub input_schema: Value,
+    pub endpoint: &'a Endpoint,
+    /// Base URL of the endpoint, its own or its group's
+    pub base: &'a str,
+}
+
+/// One tool per endpoint, in catalog order
+pub fn tools(catalog: &ApiCatalog) -> Vec<Tool<'_>> {
+    let mut names: Vec<String> = Vec::new();
+    catalog
+        .api_groups
+        .iter()
+        .flat_map(|group| {
+            group
+                .endpoints
+                .iter()
+                .map(move |endpoint| (group, endpoint))
+        })
+        .map(|(group, endpoint)| Tool {
+            name: unique_name(&endpoint.text, &mut names),
+            description: description(endpoint),
+            input_schema: input_schema(endpoint),
+            endpoint,
+            base: endpoint.base.as_deref().unwrap_or(&group.base).trim(),
+        })
+        .collect()
+}
+
+/// `[{"type": "function", "function": {"name", "description", "parameters"}}]`
+/// as sent in the `tools` of OpenAI chat completions
+pub fn openai_functions(catalog: &ApiCatalog) -> Value {
+    Value::Sequence(
+        tools(catalog)
+            .into_iter()
+            .map(|tool| {
+                mapping([
+                    ("type", "function".into()),
+                    (
+                        "function",
+                        mapping([
+                            ("name", tool.name.into()),
+                            ("description", tool.description.into()),
+                            ("parameters", tool.input_schema),
+                        ]),
+                    ),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// `[{"name", "description", "input_schema"}]` as sent in the `tools` of
+/// Anthropic messages
+pub fn anthropic_tools(catalog: &ApiCatalog) -> Value {
+    Value::Sequence(
+        tools(catalog)
+            .into_iter()
+            .map(|tool| {
+                mapping([
+                    ("name", tool.name.into()),
+                    ("description", tool.description.into()),
+                    ("input_schema", tool.input_schema),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// `{"tools": [{"name", "description", "inputSchema"}]}`, the result of an
+/// MCP `tools/list` request
+pub fn mcp_tools(catalog: &ApiCatalog) -> Value {
+    mapping([(
+        "tools",
+        Value::Sequence(tools(catalog).into_iter().map(mcp_tool).collect()),
+    )])
+}
+
+fn mcp_tool(tool: Tool) -> Value {
+    mapping([
+        ("name", tool.name.into()),
+        ("description", tool.description.into()),
+        ("inputSchema", tool.input_schema),
+    ])
+}
+
+/// The endpoint description, or its text when it has none, with the
+/// suggested sentence as an example request
+fn description(endpoint: &Endpoint) -> String {
+    let description = if endpoint.description.trim().is_empty() {
+        endpoint.text.trim()
+    } else {
+        endpoint.description.trim()
+    };
+    if endpoint.suggested_sentence.trim().is_empty() {
+        description.to_string()
+    } else {
+        format!(
+            "{}. Example: \"{}\"",
+            description.trim_end_matches('.'),
+            endpoint.suggested_sentence.trim()
+        )
+    }
+}
+
+/// Placeholders of the path are required whatever the catalog says, and
+/// listed even when they have no parameter, since the call needs them
+fn input_schema(endpoint: &Endpoint) -> Value {
+    let placeholders = path_placeholders(&endpoint.path);
+    let mut properties = serde_yaml::Mapping::new();
+    let mut required: Vec<Value> = Vec::new();
+    for parameter in &endpoint.parameters {
+        properties.insert(parameter.name.as_str().into(), property(parameter));
+        if parameter.required || placeholders.contains(&parameter.name.as_str()) {
+            required.push(parameter.name.as_str().into());
+        }
+    }
+    for placeholder in placeholders {
+        if !properties.contains_key(placeholder) {
+            properties.insert(placeholder.into(), mapping([("type", "string".into())]));
+            required.push(placeholder.into());
+        }
+    }
+
+    mapping([
+        ("type", "object".into()),
+        ("properties", Value::Mapping(properties)),
+        ("required", Value::Sequence(required)),
+    ])
+}
+
+/// Alternatives are other names of the parameter, not allowed values, so
+/// they are hinted in the description rather than declared as an `enum`
+fn property(parameter: &Parameter) -> Value {
+    let mut description = parameter.description.trim().to_string();
+    if !parameter.alternatives.is_empty() {
+        let hint = format!("Also known as: {}", parameter.alternatives.join(", "));
+        description = if description.is_empty() {
+            hint
+        } else {
+            format!("{} ({})", description.trim_end_matches('.'), hint)
+        };
+    }
+
+    let mut property = serde_yaml::Mapping::new();
+    property.insert("type".into(), "string".into());
+    if !description.is_empty() {
+        property.insert("description".into(), description.into());
+    }
+    Value::Mapping(property)
+}
+
+/// snake_case of the endpoint text limited to the characters and length
+/// tool names allow, numbered when already taken
+fn unique_name(text: &str, taken: &mut Vec<String>) -> String {
+    let mut base = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_");
+    if base.is_empty() {
+        base = "endpoint".to_string();
+    }
+    base.truncate(MAX_NAME_LENGTH - 3);
+
+    let mut name = base.clone();
+    let mut count = 1;
+    while taken.contains(&name) {
+        count += 1;
+        name = format!("{}_{}", base, count);
+    }
+    taken.push(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+api_groups:
+  - name: Documents
+    base: https://api.example.com
+    endpoints:
+      - text: Upload document
+        description: Upload a document for an event
+        suggested_sentence: Upload report.pdf for event 12
+        verb: POST
+        path: /documents
+        parameters:
+          - name: file
+            description: File to upload
+            required: true
+          - name: idEvenement
+            description: Event identifier.
+            required: true
+            alternatives: [event_id, evenement_id]
+          - name: tag
+            required: false
+      - text: Upload document!
+        verb: POST
+        path: /v2/documents
+"#;
+
+    #[test]
+    fn builds_json_schemas_with_required_arrays() {
+        let catalog = ApiCatalog::from_yaml(CATALOG).unwrap();
+
+        let functions = openai_functions(&catalog);
+        let function = &functions[0]["function"];
+        assert_eq!(functions[0]["type"], "function");
+        assert_eq!(function["name"], "upload_document");
+        assert_eq!(
+            function["description"],
+            "Upload a document for an event. Example: \"Upload report.pdf for event 12\""
+        );
+        assert_eq!(
+            function["parameters"]["required"],
+            Value::Sequence(vec!["file".into(), "idEvenement".into()])
+        );
+        assert_eq!(
+            function["parameters"]["properties"]["idEvenement"]["description"],
+            "Event identifier (Also known as: event_id, evenement_id)"
+        );
+        assert!(function["parameters"]["properties"]["tag"]
+            .get("description")
+            .is_none());
+
+        let tools = anthropic_tools(&catalog);
+        assert_eq!(tools[1]["name"], "upload_document_2");
+        assert_eq!(tools[1]["description"], "Upload document!");
+        assert_eq!(
+            tools[1]["input_schema"]["required"],
+            Value::Sequence(vec![])
+        );
+
+        let mcp = mcp_tools(&catalog);
+        assert_eq!(mcp["tools"][0]["inputSchema"]["type"], "object");
+    }
+
+    #[test]
+    fn requires_path_placeholders() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Orders
+    endpoints:
+      - text: Get order
+        verb: GET
+        path: /users/{userId}/orders/{ orderId }
+        parameters:
+          - name: userId
+            required: false
+"#,
+        )
+        .unwrap();
+        let schema = &mcp_tools(&catalog)["tools"][0]["inputSchema"];
+        assert_eq!(
+            schema["required"],
+            Value::Sequence(vec!["userId".into(), "orderId".into()])
+        );
+        assert_eq!(schema["properties"]["orderId"]["type"], "string");
+    }
+
+    #[test]
+    fn limits_tool_names() {
+        let mut taken = Vec::new();
+        let name = unique_name(&"very long endpoint text ".repeat(10), &mut taken);
+        assert!(name.len() <= MAX_NAME_LENGTH);
+        assert_eq!(unique_name("Get user (v2)", &mut taken), "get_user_v2");
+    }
+}
@@ -1,72 +1,670 @@
+use futures_util::stream::{self, StreamExt};
 use graflog::app_log;
-use reqwest::Client;
-use std::env;
 use std::error::Error;
 use std::fs;
 
 use crate::{
+    catalog::ApiCatalog,
+    catalog_validator::{self, ValidationIssue},
+    chunking::{self, ChunkingOptions},
+    completeness::{self, Reconciliation},
+    enrichment,
     extract_yaml::extract_yaml,
+    importers,
+    llm_provider::{CompletionOptions, LlmProvider},
     load_prompt::load_prompt,
-    models::{ChatMessage, CohereRequest, CohereResponse},
+    sentence_coverage,
+    yaml_repair::TemplateSchema,
     yaml_validator,
 };
 
-pub async fn format_yaml_with_cohere(
-    input_file_path: &str,
-    template_file_path: &str,
-    system_prompt_path: &str,
-    user_prompt_path: &str,
-) -> Result<String, Box<dyn Error>> {
-    // Load environment variables
-    dotenv::dotenv().ok();
-    let api_key = env::var("COHERE_API_KEY")
-        .map_err(|_| "COHERE_API_KEY not found in environment variables")?;
-
-    // Read files (same as before)
-    let input_content = fs::read_to_string(input_file_path)?;
-    let template_content = fs::read_to_string(template_file_path)?;
-    let system_prompt = load_prompt(system_prompt_path)?;
-    let user_prompt_template = load_prompt(user_prompt_path)?;
-
-    let user_prompt = user_prompt_template
-        .replace("{INPUT_CONTENT}", &input_content)
-        .replace("{TEMPLATE_CONTENT}", &template_content);
-
-    // Prepare Cohere request
-    let client = Client::new();
-    let request = CohereRequest {
-        model: "command-r7b-12-2024".to_string(), // or "command-r-08-2024"
-        // model:     "command".to_string(), // or "command-nightly", "command-light"
-        message: user_prompt,
-        max_tokens: Some(4000),
-        temperature: Some(0.1),
-        chat_history: vec![ChatMessage {
-            role: "SYSTEM".to_string(),
-            message: system_prompt,
-        }],
-    };
-
-    app_log!(info, "Calling Cohere API");
-    let resp = client
-        .post("https://api.cohere.ai/v1/chat")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        let error_text = resp.text().await?;
-        app_log!(error, "Failed to call Cohere: {}", error_text);
-        return Err(format!("Cohere API error: {}", error_text).into());
-    }
-
-    let cohere_response: CohereResponse = resp.json().await?;
-    app_log!(info, "Received response from Cohere");
-
-    // Extract and validate YAML (same as before)
-    let yaml_content = extract_yaml(&cohere_response.text);
-    let fixed_yaml = yaml_validator::validate_and_fix_yaml(&yaml_content)?;
-
-    Ok(fixed_yaml)
+/// Prompts and template shared by every call made for one uploaded file
+struct PromptSet {
+    template_content: String,
+    /// Nesting of the template, used to re-indent broken answers
+    schema: TemplateSchema,
+    system_prompt: String,
+    user_prompt_template: String,
+}
+
+impl PromptSet {
+    fn load(
+        template_file_path: &str,
+        system_prompt_path: &str,
+        user_prompt_path: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let template_content = fs::read_to_string(template_file_path)?;
+        Ok(PromptSet {
+            schema: TemplateSchema::from_template(&template_content),
+            template_content,
+            system_prompt: load_prompt(system_prompt_path)?,
+            user_prompt_template: load_prompt(user_prompt_path)?,
+        })
+    }
+
+    fn user_prompt(&self, input_content: &str) -> String {
+        self.user_prompt_template
+            .replace("{INPUT_CONTENT}", input_content)
+            .replace("{TEMPLATE_CONTENT}", &self.template_content)
+    }
+}
+
+/// A formatted `api_groups` document and how its endpoints compare with the input
+pub struct FormattedCatalog {
+    pub yaml: String,
+    /// The document parsed, for the exporters
+    pub catalog: ApiCatalog,
+    /// Format converted natively, None when the LLM formatted the whole input
+    pub input_format: Option<&'static str>,
+    /// None when the endpoints of a free-text input cannot be counted, or
+    /// when a native import converted every endpoint
+    pub reconciliation: Option<Reconciliation>,
+    /// Semantic rule violations, the document is returned regardless
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Runs uploaded files through the configured LLM provider and YAML validation
+pub struct LlmFormatter {
+    provider: Box<dyn LlmProvider>,
+    options: CompletionOptions,
+    chunking: Option<ChunkingOptions>,
+    completeness_retries: usize,
+    repair: Option<RepairOptions>,
+    sentence_prompt: Option<String>,
+    enrichment_prompt: Option<String>,
+}
+
+/// Bounded LLM repair of documents the local YAML fixes cannot save
+pub struct RepairOptions {
+    pub attempts: usize,
+    /// Prompt with {YAML_CONTENT} and {ERROR} placeholders
+    pub prompt_template: String,
+}
+
+impl LlmFormatter {
+    pub fn new(provider: Box<dyn LlmProvider>, options: CompletionOptions) -> Self {
+        LlmFormatter {
+            provider,
+            options,
+            chunking: None,
+            completeness_retries: 1,
+            repair: None,
+            sentence_prompt: None,
+            enrichment_prompt: None,
+        }
+    }
+
+    pub fn with_chunking(mut self, chunking: Option<ChunkingOptions>) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    pub fn with_completeness_retries(mut self, retries: usize) -> Self {
+        self.completeness_retries = retries;
+        self
+    }
+
+    pub fn with_repair(mut self, repair: Option<RepairOptions>) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    /// Regenerates suggested sentences that fail the coverage check with a
+    /// prompt holding an {ENDPOINTS} placeholder
+    pub fn with_sentence_regeneration(mut self, prompt_template: Option<String>) -> Self {
+        self.sentence_prompt = prompt_template;
+        self
+    }
+
+    /// Fills the fields a native import leaves blank with a prompt holding
+    /// an {ENDPOINTS} placeholder
+    pub fn with_enrichment(mut self, prompt_template: Option<String>) -> Self {
+        self.enrichment_prompt = prompt_template;
+        self
+    }
+
+    /// Formats a file with a single prompt, whatever the template describes
+    pub async fn format_file(
+        &self,
+        input_file_path: &str,
+        template_file_path: &str,
+        system_prompt_path: &str,
+        user_prompt_path: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let input_content = fs::read_to_string(input_file_path)?;
+        let prompts = PromptSet::load(template_file_path, system_prompt_path, user_prompt_path)?;
+
+        self.format_content(&input_content, &prompts).await
+    }
+
+    /// Formats a file into an `api_groups` catalog. Inputs in a known format
+    /// are converted natively; anything else goes to the LLM, split into
+    /// chunks when chunking is configured, with re-prompts for endpoints of
+    /// the input that the model left out
+    pub async fn format_catalog_file(
+        &self,
+        input_file_path: &str,
+        template_file_path: &str,
+        system_prompt_path: &str,
+        user_prompt_path: &str,
+    ) -> Result<FormattedCatalog, Box<dyn Error>> {
+        let input_content = fs::read_to_string(input_file_path)?;
+        let prompts = PromptSet::load(template_file_path, system_prompt_path, user_prompt_path)?;
+
+        let (yaml, reconciliation, input_format) = match self.import(&input_content, &prompts).await
+        {
+            Some((yaml, format)) => (yaml, None, Some(format)),
+            None => {
+                let yaml = self.format_chunked(&input_content, &prompts).await?;
+                let (yaml, reconciliation) = self.reconcile(&input_content, yaml, &prompts).await?;
+                (yaml, reconciliation, None)
+            }
+        };
+
+        // Parsing is not enough, the document must also match the catalog schema
+        let mut catalog = ApiCatalog::from_yaml(&yaml)?;
+        let yaml = match &self.sentence_prompt {
+            Some(prompt_template) => {
+                let (yaml, replaced) = self
+                    .regenerate_sentences(yaml, &catalog, prompt_template, &prompts)
+                    .await;
+                if replaced > 0 {
+                    catalog = ApiCatalog::from_yaml(&yaml)?;
+                }
+                yaml
+            }
+            None => yaml,
+        };
+        let issues = catalog_validator::validate_catalog(&catalog);
+        if !issues.is_empty() {
+            app_log!(
+                warn,
+                "Formatted catalog has {} validation issues",
+                issues.len()
+            );
+        }
+
+        Ok(FormattedCatalog {
+            yaml,
+            catalog,
+            input_format,
+            reconciliation,
+            issues,
+        })
+    }
+
+    /// Converts inputs in a known format without the LLM, then asks the
+    /// provider only for the fields the conversion leaves blank. Returns
+    /// None when the format is unknown or the conversion fails.
+    async fn import(
+        &self,
+        input_content: &str,
+        prompts: &PromptSet,
+    ) -> Option<(String, &'static str)> {
+        let imported = match importers::import(input_content)? {
+            Ok(imported) => imported,
+            Err(e) => {
+                app_log!(
+                    warn,
+                    "Native import failed, formatting with {} instead: {}",
+                    self.provider.name(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        let mut catalog = imported.catalog;
+        app_log!(
+            info,
+            "Imported {} input natively ({} groups)",
+            imported.format,
+            catalog.api_groups.len()
+        );
+        if let Some(prompt_template) = &self.enrichment_prompt {
+            self.enrich(&mut catalog, imported.enrich, prompt_template, prompts)
+                .await;
+        }
+
+        match serde_yaml::to_string(&catalog) {
+            Ok(yaml) => Some((yaml, imported.format)),
+            Err(e) => {
+                app_log!(error, "Could not serialize imported catalog: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Requests the blank fields of imported endpoints, a chunk of endpoints
+    /// per call when chunking is configured. Failed calls are logged and
+    /// leave their endpoints as imported.
+    async fn enrich(
+        &self,
+        catalog: &mut ApiCatalog,
+        fields: &[&'static str],
+        prompt_template: &str,
+        prompts: &PromptSet,
+    ) {
+        let targets = enrichment::targets(catalog, fields);
+        if targets.is_empty() {
+            return;
+        }
+
+        let (batch_size, parallelism) = match &self.chunking {
+            Some(chunking) => (chunking.endpoints_per_chunk, chunking.parallelism),
+            None => (targets.len(), 1),
+        };
+        app_log!(
+            info,
+            "Requesting blank fields of {} endpoints from {}",
+            targets.len(),
+            self.provider.name()
+        );
+
+        let snapshot: &ApiCatalog = catalog;
+        let answers: Vec<Result<String, Box<dyn Error>>> = stream::iter(targets.chunks(batch_size))
+            .map(|batch| async move {
+                let input = enrichment::enrichment_input(snapshot, batch)?;
+                let prompt = prompt_template.replace("{ENDPOINTS}", &input);
+                let response = self
+                    .provider
+                    .complete(&prompts.system_prompt, &prompt, &self.options)
+                    .await?;
+                Ok(extract_yaml(&response))
+            })
+            .buffered(parallelism)
+            .collect()
+            .await;
+
+        let mut changed = 0;
+        for (batch, answer) in targets.chunks(batch_size).zip(answers) {
+            match answer.and_then(|answer| enrichment::apply_enrichment(catalog, batch, &answer)) {
+                Ok(count) => changed += count,
+                Err(e) => app_log!(error, "Enrichment request failed: {}", e),
+            }
+        }
+        app_log!(
+            info,
+            "Filled blank fields of {} of {} endpoints",
+            changed,
+            targets.len()
+        );
+    }
+
+    async fn format_chunked(
+        &self,
+        input_content: &str,
+        prompts: &PromptSet,
+    ) -> Result<String, Box<dyn Error>> {
+        let chunking = match &self.chunking {
+            Some(chunking) => chunking,
+            None => return self.format_content(input_content, prompts).await,
+        };
+
+        let chunks = chunking::split_input(input_content, chunking.endpoints_per_chunk);
+        if chunks.len() == 1 {
+            return self.format_content(input_content, prompts).await;
+        }
+
+        app_log!(
+            info,
+            "Formatting input in {} chunks ({} at a time)",
+            chunks.len(),
+            chunking.parallelism
+        );
+        let total = chunks.len();
+        let results: Vec<Result<String, Box<dyn Error>>> = stream::iter(chunks.iter().enumerate())
+            .map(|(index, chunk)| async move {
+                app_log!(info, "Formatting chunk {}/{}", index + 1, total);
+                self.format_content(chunk, prompts)
+                    .await
+                    .map_err(|e| format!("Chunk {}/{} failed: {}", index + 1, total, e).into())
+            })
+            .buffered(chunking.parallelism)
+            .collect()
+            .await;
+
+        let formatted = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+        chunking::merge_api_groups(&formatted)
+    }
+
+    /// Compares endpoint counts and asks again for the missing endpoints only
+    async fn reconcile(
+        &self,
+        input_content: &str,
+        mut yaml: String,
+        prompts: &PromptSet,
+    ) -> Result<(String, Option<Reconciliation>), Box<dyn Error>> {
+        let expected = match completeness::input_endpoints(input_content) {
+            Some(expected) => expected,
+            None => return Ok((yaml, None)),
+        };
+
+        let formatted = completeness::output_endpoints(&yaml)?;
+        let initial_count = formatted.len();
+        let mut formatted_count = initial_count;
+        let mut missing = completeness::missing_endpoints(&expected, &formatted);
+
+        let mut attempt = 0;
+        while !missing.is_empty() && attempt < self.completeness_retries {
+            attempt += 1;
+            app_log!(
+                warn,
+                "{} of {} endpoints missing, re-requesting them (attempt {}/{})",
+                missing.len(),
+                expected.len(),
+                attempt,
+                self.completeness_retries
+            );
+
+            let missing_input = completeness::missing_input(&missing)?;
+            match self.format_content(&missing_input, prompts).await {
+                Ok(extra) => {
+                    yaml = chunking::merge_api_groups(&[yaml, extra])?;
+                    let formatted = completeness::output_endpoints(&yaml)?;
+                    formatted_count = formatted.len();
+                    missing = completeness::missing_endpoints(&expected, &formatted);
+                }
+                Err(e) => app_log!(error, "Re-request for missing endpoints failed: {}", e),
+            }
+        }
+
+        let reconciliation = Reconciliation {
+            input_endpoints: expected.len(),
+            output_endpoints: formatted_count,
+            recovered: formatted_count.saturating_sub(initial_count),
+            missing: missing.iter().map(completeness::endpoint_label).collect(),
+        };
+        reconciliation.log();
+
+        Ok((yaml, Some(reconciliation)))
+    }
+
+    /// Asks the provider for new suggested sentences of the flagged endpoints
+    /// only. Failures are logged and leave the document as it was.
+    async fn regenerate_sentences(
+        &self,
+        yaml: String,
+        catalog: &ApiCatalog,
+        prompt_template: &str,
+        prompts: &PromptSet,
+    ) -> (String, usize) {
+        let flagged = sentence_coverage::flagged_sentences(catalog);
+        if flagged.is_empty() {
+            return (yaml, 0);
+        }
+
+        app_log!(
+            info,
+            "Regenerating {} suggested sentences with {}",
+            flagged.len(),
+            self.provider.name()
+        );
+        let result = async {
+            let input = sentence_coverage::regeneration_input(catalog, &flagged)?;
+            let prompt = prompt_template.replace("{ENDPOINTS}", &input);
+            let response = self
+                .provider
+                .complete(&prompts.system_prompt, &prompt, &self.options)
+                .await?;
+            sentence_coverage::apply_sentences(&yaml, catalog, &flagged, &extract_yaml(&response))
+        }
+        .await;
+
+        match result {
+            Ok((updated, replaced)) => {
+                app_log!(
+                    info,
+                    "Replaced {} of {} suggested sentences",
+                    replaced,
+                    flagged.len()
+                );
+                (updated, replaced)
+            }
+            Err(e) => {
+                app_log!(error, "Suggested sentence regeneration failed: {}", e);
+                (yaml, 0)
+            }
+        }
+    }
+
+    async fn format_content(
+        &self,
+        input_content: &str,
+        prompts: &PromptSet,
+    ) -> Result<String, Box<dyn Error>> {
+        let user_prompt = prompts.user_prompt(input_content);
+
+        app_log!(info, "Requesting formatting from {}", self.provider.name());
+        let response = self
+            .provider
+            .complete(&prompts.system_prompt, &user_prompt, &self.options)
+            .await?;
+
+        // Extract and validate YAML
+        let yaml_content = extract_yaml(&response);
+        match yaml_validator::validate_and_fix_yaml(&yaml_content, &prompts.schema) {
+            Ok(fixed_yaml) => Ok(fixed_yaml),
+            Err(e) => self.repair_yaml(yaml_content, e, prompts).await,
+        }
+    }
+
+    /// Sends the broken YAML and its parser error back to the provider until
+    /// it validates or the configured number of attempts is spent
+    async fn repair_yaml(
+        &self,
+        mut broken_yaml: String,
+        mut last_error: Box<dyn Error>,
+        prompts: &PromptSet,
+    ) -> Result<String, Box<dyn Error>> {
+        let repair = match &self.repair {
+            Some(repair) if repair.attempts > 0 => repair,
+            _ => return Err(last_error),
+        };
+
+        for attempt in 1..=repair.attempts {
+            let error_description = yaml_validator::describe_yaml_error(&broken_yaml)
+                .unwrap_or_else(|| last_error.to_string());
+            app_log!(
+                warn,
+                "Asking {} to repair YAML (attempt {}/{}): {}",
+                self.provider.name(),
+                attempt,
+                repair.attempts,
+                error_description.lines().next().unwrap_or_default()
+            );
+
+            let repair_prompt = repair
+                .prompt_template
+                .replace("{ERROR}", &error_description)
+                .replace("{YAML_CONTENT}", &broken_yaml);
+            let response = self
+                .provider
+                .complete(&prompts.system_prompt, &repair_prompt, &self.options)
+                .await?;
+
+            let candidate = extract_yaml(&response);
+            match yaml_validator::validate_and_fix_yaml(&candidate, &prompts.schema) {
+                Ok(fixed_yaml) => {
+                    app_log!(info, "YAML repaired on attempt {}", attempt);
+                    return Ok(fixed_yaml);
+                }
+                Err(e) => {
+                    app_log!(warn, "Repair attempt {} still invalid: {}", attempt, e);
+                    broken_yaml = candidate;
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(format!(
+            "Failed to fix YAML after {} repair attempts: {}",
+            repair.attempts, last_error
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::MockProvider;
+    use async_trait::async_trait;
+    use serde_yaml::Value;
+    use std::sync::Mutex;
+
+    /// Answers with the queued responses in order
+    struct ScriptedProvider {
+        responses: Mutex<Vec<String>>,
+    }
+
+    #[async_trait(?Send)]
+    impl LlmProvider for ScriptedProvider {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        async fn complete(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+            _options: &CompletionOptions,
+        ) -> Result<String, Box<dyn Error>> {
+            Ok(self.responses.lock().unwrap().remove(0))
+        }
+    }
+
+    const BROKEN_YAML: &str = "api_groups: [\n  - name: \"Users\"\n";
+
+    fn formatter(responses: &[&str], attempts: usize) -> LlmFormatter {
+        let provider = ScriptedProvider {
+            responses: Mutex::new(responses.iter().map(|r| r.to_string()).collect()),
+        };
+        LlmFormatter::new(Box::new(provider), CompletionOptions::default()).with_repair(Some(
+            RepairOptions {
+                attempts,
+                prompt_template: "{ERROR}\n{YAML_CONTENT}".to_string(),
+            },
+        ))
+    }
+
+    fn prompts() -> PromptSet {
+        PromptSet {
+            template_content: String::new(),
+            schema: TemplateSchema::default(),
+            system_prompt: String::new(),
+            user_prompt_template: "{INPUT_CONTENT}".to_string(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn repairs_invalid_yaml_with_the_provider() {
+        let formatter = formatter(&[BROKEN_YAML, "api_groups:\n  - name: Users\n"], 2);
+
+        let yaml = formatter.format_content("input", &prompts()).await.unwrap();
+        assert_eq!(yaml, "api_groups:\n  - name: Users");
+    }
+
+    #[actix_web::test]
+    async fn gives_up_after_the_configured_attempts() {
+        let formatter = formatter(&[BROKEN_YAML, BROKEN_YAML], 1);
+
+        let err = formatter
+            .format_content("input", &prompts())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 1 repair attempts"));
+    }
+
+    #[actix_web::test]
+    async fn regenerates_only_flagged_sentences() {
+        let yaml = "api_groups:\n  - name: Users\n    endpoints:\n      - text: Get user\n        suggested_sentence: Get user {id}\n        verb: GET\n        path: /users/{id}\n        parameters:\n          - name: id\n            required: true\n      - text: List users\n        suggested_sentence: List all users\n        verb: GET\n        path: /users\n";
+        let catalog = ApiCatalog::from_yaml(yaml).unwrap();
+        let formatter = formatter(&["- id: 0\n  suggested_sentence: Get user with id 42\n"], 0);
+
+        let (updated, replaced) = formatter
+            .regenerate_sentences(yaml.to_string(), &catalog, "{ENDPOINTS}", &prompts())
+            .await;
+        assert_eq!(replaced, 1);
+
+        let updated = ApiCatalog::from_yaml(&updated).unwrap();
+        let sentences: Vec<&str> = updated.api_groups[0]
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.suggested_sentence.as_str())
+            .collect();
+        assert_eq!(sentences, vec!["Get user with id 42", "List all users"]);
+    }
+
+    #[actix_web::test]
+    async fn formats_chunks_and_merges_them_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = "- name: List users\n- name: Create user\n- name: Delete user\n- name: List orders\n- name: Cancel order\n";
+        let input_path = dir.path().join("input.yaml");
+        fs::write(&input_path, input).unwrap();
+
+        // One fixture per chunk, keyed by the prompt the chunk is sent with
+        let (template, system, user) = (
+            "template.yaml",
+            "prompt/system_prompt.txt",
+            "prompt/user_prompt.txt",
+        );
+        let prompts = PromptSet::load(template, system, user).unwrap();
+        let answers = [
+            "api_groups:\n  - name: Users\n    endpoints:\n      - {text: List users, verb: GET, path: /users}\n      - {text: Create user, verb: POST, path: /users}\n",
+            "api_groups:\n  - name: Users\n    endpoints:\n      - {text: Delete user, verb: DELETE, path: '/users/{id}'}\n  - name: Orders\n    endpoints:\n      - {text: List orders, verb: GET, path: /orders}\n",
+            "api_groups:\n  - name: Orders\n    endpoints:\n      - {text: Cancel order, verb: DELETE, path: '/orders/{id}'}\n",
+        ];
+        let chunks = chunking::split_input(input, 2);
+        assert_eq!(chunks.len(), answers.len());
+        for (chunk, answer) in chunks.iter().zip(answers) {
+            let hash = crate::providers::mock::prompt_hash(&prompts.user_prompt(chunk));
+            fs::write(dir.path().join(format!("{}.yaml", hash)), answer).unwrap();
+        }
+
+        let formatter = LlmFormatter::new(
+            Box::new(MockProvider::with_fixture_dir(dir.path())),
+            CompletionOptions::default(),
+        )
+        .with_chunking(Some(ChunkingOptions {
+            endpoints_per_chunk: 2,
+            parallelism: 2,
+        }));
+        let formatted = formatter
+            .format_catalog_file(input_path.to_str().unwrap(), template, system, user)
+            .await
+            .unwrap();
+
+        let document: Value = serde_yaml::from_str(&formatted.yaml).unwrap();
+        let groups: Vec<(&str, Vec<&str>)> = document["api_groups"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|group| {
+                let texts = group["endpoints"]
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|e| e["text"].as_str().unwrap())
+                    .collect();
+                (group["name"].as_str().unwrap(), texts)
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("Users", vec!["List users", "Create user", "Delete user"]),
+                ("Orders", vec!["List orders", "Cancel order"]),
+            ]
+        );
+        let reconciliation = formatted.reconciliation.unwrap();
+        assert_eq!(
+            (
+                reconciliation.input_endpoints,
+                reconciliation.output_endpoints
+            ),
+            (5, 5)
+        );
+        assert!(reconciliation.missing.is_empty());
+    }
 }
@@ -0,0 +1,518 @@
+use serde_yaml::Value;
+use std::error::Error;
+
+use super::har::collapse_identifiers;
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+/// Options whose value is the next argument and that the import ignores
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "-A",
+    "--user-agent",
+    "-e",
+    "--referer",
+    "-b",
+    "--cookie",
+    "-c",
+    "--cookie-jar",
+    "-u",
+    "--user",
+    "-m",
+    "--max-time",
+    "-w",
+    "--write-out",
+    "-x",
+    "--proxy",
+    "-T",
+    "--upload-file",
+    "-E",
+    "--cert",
+    "-K",
+    "--config",
+    "-r",
+    "--range",
+    "--connect-timeout",
+    "--retry",
+    "--resolve",
+    "--cacert",
+    "--key",
+    "--oauth2-bearer",
+    "--limit-rate",
+    "-y",
+    "-Y",
+    "-z",
+    "-C",
+];
+
+/// A command parsed from its arguments
+#[derive(Default)]
+struct Command {
+    method: Option<String>,
+    url: Option<String>,
+    content_type: String,
+    data: Vec<String>,
+    form: Vec<String>,
+    /// `-G`: data goes to the query string
+    get: bool,
+    head: bool,
+}
+
+/// True when the input is a list of curl commands, optionally with `#`
+/// comments, and nothing else
+pub fn detect(input_content: &str) -> bool {
+    let (commands, only_commands) = commands(input_content);
+    only_commands && !commands.is_empty()
+}
+
+/// Turns each command into an endpoint: the comment above it gives the text,
+/// the method, URL path and host give the verb, path and group, and query,
+/// `-d`/`--data` and `-F` fields give the parameters. Calls to the same verb
+/// and path are merged.
+pub fn import(input_content: &str) -> Result<ApiCatalog, Box<dyn Error>> {
+    let mut groups: Vec<ApiGroup> = Vec::new();
+
+    for (comment, command_line) in commands(input_content).0 {
+        let command = parse(&words(&command_line));
+        let url = match &command.url {
+            Some(url) => url.clone(),
+            None => continue,
+        };
+        let (base, path, query) = split_url(&url);
+        let host = base.split("://").nth(1).unwrap_or(&base).to_string();
+
+        let method = match (&command.method, command.head) {
+            (Some(method), _) => method.to_uppercase(),
+            (None, true) => "HEAD".to_string(),
+            (None, false)
+                if !command.get && (!command.data.is_empty() || !command.form.is_empty()) =>
+            {
+                "POST".to_string()
+            }
+            (None, false) => "GET".to_string(),
+        };
+
+        let mut parameters: Vec<Parameter> = Vec::new();
+        for name in path_placeholders(&path) {
+            add_parameter(&mut parameters, name, true);
+        }
+        for name in query_names(&query) {
+            add_parameter(&mut parameters, &name, false);
+        }
+        for name in data_fields(&command) {
+            // With -G the data is sent as query parameters
+            add_parameter(&mut parameters, &name, !command.get);
+        }
+        for field in &command.form {
+            if let Some((name, _)) = field.split_once('=') {
+                add_parameter(&mut parameters, name.trim(), true);
+            }
+        }
+
+        let group = match groups.iter_mut().position(|group| group.name == host) {
+            Some(index) => &mut groups[index],
+            None => {
+                groups.push(ApiGroup {
+                    name: host.clone(),
+                    description: String::new(),
+                    base: base.clone(),
+                    endpoints: Vec::new(),
+                });
+                groups.last_mut().unwrap()
+            }
+        };
+        match group
+            .endpoints
+            .iter_mut()
+            .find(|endpoint| endpoint.verb == method && endpoint.path == path)
+        {
+            Some(endpoint) => {
+                for parameter in parameters {
+                    if !endpoint
+                        .parameters
+                        .iter()
+                        .any(|known| known.name == parameter.name)
+                    {
+                        endpoint.parameters.push(parameter);
+                    }
+                }
+            }
+            None => group.endpoints.push(Endpoint {
+                text: comment.unwrap_or_else(|| format!("{} {}", method, path)),
+                verb: method,
+                path,
+                parameters,
+                ..Default::default()
+            }),
+        }
+    }
+
+    if groups.is_empty() {
+        return Err("No curl command with a URL found".into());
+    }
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+fn is_curl(line: &str) -> bool {
+    let line = line.strip_prefix("$ ").unwrap_or(line).trim_start();
+    line == "curl" || line.starts_with("curl ")
+}
+
+/// Commands with their continuation lines joined, each with the comment
+/// right above it, and whether the input has no other lines than commands,
+/// comments and blank lines
+fn commands(input_content: &str) -> (Vec<(Option<String>, String)>, bool) {
+    let mut commands = Vec::new();
+    let mut only_commands = true;
+    let mut comment: Option<String> = None;
+    let mut current: Option<String> = None;
+
+    for line in input_content.lines() {
+        let trimmed = line.trim();
+        if let Some(command) = current.as_mut() {
+            command.push(' ');
+            command.push_str(trimmed.trim_end_matches('\\'));
+            if !trimmed.ends_with('\\') {
+                commands.push((comment.take(), current.take().unwrap_or_default()));
+            }
+            continue;
+        }
+
+        if let Some(text) = trimmed.strip_prefix('#') {
+            let text = text.trim();
+            if !text.is_empty() {
+                comment = Some(text.to_string());
+            }
+        } else if is_curl(trimmed) {
+            let command = trimmed.strip_prefix("$ ").unwrap_or(trimmed);
+            if command.ends_with('\\') {
+                current = Some(command.trim_end_matches('\\').to_string());
+            } else {
+                commands.push((comment.take(), command.to_string()));
+            }
+        } else if !trimmed.is_empty() {
+            comment = None;
+            only_commands = false;
+        }
+    }
+    if let Some(command) = current {
+        commands.push((comment, command));
+    }
+    (commands, only_commands)
+}
+
+/// Splits a command line into arguments the way a POSIX shell would,
+/// honouring single quotes, double quotes and backslash escapes
+fn words(command_line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command_line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    current.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                if !matches!(escaped, '"' | '\\' | '$' | '`') {
+                                    current.push('\\');
+                                }
+                                current.push(escaped);
+                            }
+                        }
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+fn parse(words: &[String]) -> Command {
+    let mut command = Command::default();
+    let mut args = words.iter().skip(1);
+
+    while let Some(arg) = args.next() {
+        let arg = arg.as_str();
+        // Short options may carry their value: -XPOST, -HAccept:...
+        let (option, attached) = match arg {
+            _ if arg.starts_with("--") => match arg.split_once('=') {
+                Some((option, value)) => (option, Some(value.to_string())),
+                None => (arg, None),
+            },
+            // Split after the option letter, which need not be one byte
+            _ if arg.starts_with('-') => match arg.char_indices().nth(2) {
+                Some((index, _)) => (&arg[..index], Some(arg[index..].to_string())),
+                None => (arg, None),
+            },
+            _ => (arg, None),
+        };
+        let mut value = || attached.clone().or_else(|| args.next().cloned());
+
+        match option {
+            "-X" | "--request" => command.method = value(),
+            "-H" | "--header" => {
+                if let Some(header) = value() {
+                    if let Some((name, header_value)) = header.split_once(':') {
+                        if name.trim().eq_ignore_ascii_case("content-type") {
+                            command.content_type = header_value.trim().to_lowercase();
+                        }
+                    }
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => command.data.extend(value()),
+            "--json" => {
+                command.content_type = "application/json".to_string();
+                command.data.extend(value());
+            }
+            "-F" | "--form" | "--form-string" => command.form.extend(value()),
+            "--url" => command.url = value(),
+            "-G" | "--get" => command.get = true,
+            "-I" | "--head" => command.head = true,
+            _ if IGNORED_WITH_VALUE.contains(&option) => {
+                value();
+            }
+            _ if option.starts_with('-') => {}
+            _ => {
+                if command.url.is_none() {
+                    command.url = Some(arg.to_string());
+                }
+            }
+        }
+    }
+    command
+}
+
+/// Base URL, path with `{placeholders}`, and query string of a command URL.
+/// `:id`, `<id>`, `$ID` and `${ID}` segments become placeholders, and
+/// numeric or UUID segments `{id}` like in HAR captures.
+fn split_url(url: &str) -> (String, String, String) {
+    let url = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+    let (url, query) = match url.split_once('?') {
+        Some((url, query)) => (url.to_string(), query.to_string()),
+        None => (url, String::new()),
+    };
+
+    let after_scheme = url.find("://").map_or(0, |index| index + 3);
+    let path_start = url[after_scheme..]
+        .find('/')
+        .map_or(url.len(), |index| after_scheme + index);
+    let base = url[..path_start].to_string();
+
+    let path = url[path_start..]
+        .split('/')
+        .map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| {
+                    segment
+                        .strip_prefix('<')
+                        .and_then(|rest| rest.strip_suffix('>'))
+                })
+                .or_else(|| {
+                    segment
+                        .strip_prefix("${")
+                        .and_then(|rest| rest.strip_suffix('}'))
+                })
+                .or_else(|| segment.strip_prefix('$'));
+            match name {
+                Some(name) if !name.is_empty() => format!("{{{}}}", name),
+                _ => segment.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (base, collapse_identifiers(&path), query)
+}
+
+fn query_names(query: &str) -> Vec<String> {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let name = pair.split('=').next()?.trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Top-level keys of JSON data, or the names of `a=1&b=2` data
+fn data_fields(command: &Command) -> Vec<String> {
+    let mut fields = Vec::new();
+    for data in &command.data {
+        let data = data.trim();
+        // @file reads the body from disk, nothing to learn from it
+        if data.starts_with('@') {
+            continue;
+        }
+        if command.content_type.contains("json") || data.starts_with('{') {
+            if let Ok(Value::Mapping(mapping)) = serde_yaml::from_str::<Value>(data) {
+                fields.extend(mapping.keys().filter_map(Value::as_str).map(str::to_string));
+            }
+        } else {
+            fields.extend(query_names(data));
+        }
+    }
+    fields
+}
+
+fn add_parameter(parameters: &mut Vec<Parameter>, name: &str, required: bool) {
+    if name.is_empty() || parameters.iter().any(|parameter| parameter.name == name) {
+        return;
+    }
+    parameters.push(Parameter {
+        name: name.to_string(),
+        required,
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMANDS: &str = r#"
+# List users
+curl -s "https://api.example.com/v1/users?page=2&limit=10" -H "Authorization: Bearer token"
+
+# Create user
+curl -X POST https://api.example.com/v1/users \
+  -H 'Content-Type: application/json' \
+  -d '{"email": "ann@example.com", "name": "Ann"}'
+
+$ curl -XDELETE https://api.example.com/v1/users/42
+curl https://api.example.com/v1/users/7 -X DELETE
+curl -F "file=@report.pdf" -F "title=Report" localhost:8080/upload
+curl -G https://api.example.com/v1/search --data-urlencode "q=rust"
+"#;
+
+    #[test]
+    fn parses_methods_urls_and_fields() {
+        assert!(detect(COMMANDS));
+        let catalog = import(COMMANDS).unwrap();
+
+        let groups: Vec<(&str, &str)> = catalog
+            .api_groups
+            .iter()
+            .map(|group| (group.name.as_str(), group.base.as_str()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("api.example.com", "https://api.example.com"),
+                ("localhost:8080", "http://localhost:8080"),
+            ]
+        );
+
+        let endpoints: Vec<(&str, &str, &str)> = catalog.api_groups[0]
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                (
+                    endpoint.text.as_str(),
+                    endpoint.verb.as_str(),
+                    endpoint.path.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                ("List users", "GET", "/v1/users"),
+                ("Create user", "POST", "/v1/users"),
+                ("DELETE /v1/users/{id}", "DELETE", "/v1/users/{id}"),
+                ("GET /v1/search", "GET", "/v1/search"),
+            ]
+        );
+
+        let parameters = |group: usize, endpoint: usize| -> Vec<(String, bool)> {
+            catalog.api_groups[group].endpoints[endpoint]
+                .parameters
+                .iter()
+                .map(|parameter| (parameter.name.clone(), parameter.required))
+                .collect()
+        };
+        assert_eq!(
+            parameters(0, 0),
+            vec![("page".into(), false), ("limit".into(), false)]
+        );
+        assert_eq!(
+            parameters(0, 1),
+            vec![("email".into(), true), ("name".into(), true)]
+        );
+        assert_eq!(parameters(0, 3), vec![("q".into(), false)]);
+        assert_eq!(
+            parameters(1, 0),
+            vec![("file".into(), true), ("title".into(), true)]
+        );
+        assert_eq!(catalog.api_groups[1].endpoints[0].verb, "POST");
+    }
+
+    #[test]
+    fn splits_quoted_arguments() {
+        assert_eq!(
+            words(r#"curl -d "a \"b\"" 'c d' e\ f"#),
+            vec!["curl", "-d", "a \"b\"", "c d", "e f"]
+        );
+    }
+
+    #[test]
+    fn splits_short_options_on_char_boundaries() {
+        let catalog =
+            import("curl -éx https://a.example.com/x\ncurl -XPUT https://a.example.com/y").unwrap();
+        let endpoints: Vec<(&str, &str)> = catalog.api_groups[0]
+            .endpoints
+            .iter()
+            .map(|endpoint| (endpoint.verb.as_str(), endpoint.path.as_str()))
+            .collect();
+        assert_eq!(endpoints, vec![("GET", "/x"), ("PUT", "/y")]);
+    }
+
+    #[test]
+    fn ignores_other_text() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert!(!detect(&input));
+
+        let prose = "The billing API has endpoints for invoices and refunds.\n\nCheck that it is up with:\n\ncurl https://api.example.com/health\n\nInvoices can be listed, created and voided.\n";
+        assert!(!detect(prose));
+    }
+}
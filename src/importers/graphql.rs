@@ -0,0 +1,484 @@
+use std::error::Error;
+
+use super::humanize;
+use super::tokens::{Cursor, Punctuation};
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+
+/// Path every operation is posted to
+const GRAPHQL_PATH: &str = "/graphql";
+
+/// Keywords that start a type system definition
+const DEFINITIONS: &[&str] = &[
+    "schema",
+    "extend",
+    "type",
+    "interface",
+    "input",
+    "enum",
+    "scalar",
+    "union",
+    "directive",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Description(String),
+    Punct(char),
+}
+
+impl Punctuation for Token {
+    fn punct(&self) -> Option<char> {
+        match self {
+            Token::Punct(c) => Some(*c),
+            _ => None,
+        }
+    }
+}
+
+/// A field of an object type, with its arguments
+struct Field {
+    name: String,
+    description: String,
+    arguments: Vec<Parameter>,
+}
+
+/// True for schemas in the GraphQL schema definition language
+pub fn detect(input_content: &str) -> bool {
+    let tokens = match tokenize(input_content) {
+        Some(tokens) => tokens,
+        None => return false,
+    };
+    tokens.windows(3).any(|window| {
+        matches!(
+            window,
+            [Token::Name(keyword), Token::Name(name), Token::Punct('{')]
+                if keyword == "type" && (name == "Query" || name == "Mutation")
+        )
+    }) || tokens.windows(2).any(
+        |window| matches!(window, [Token::Name(keyword), Token::Punct('{')] if keyword == "schema"),
+    )
+}
+
+/// Maps each field of the query and mutation root types to a POST endpoint
+/// on `/graphql`, with its arguments as parameters. Non-null arguments
+/// without a default value are required, and doc strings give the
+/// descriptions.
+pub fn import(input_content: &str) -> Result<ApiCatalog, Box<dyn Error>> {
+    let tokens = tokenize(input_content).ok_or("Unterminated string in GraphQL schema")?;
+    let mut parser = Parser {
+        tokens: Cursor::new(tokens),
+    };
+
+    let mut roots = vec![
+        ("query", "Query".to_string()),
+        ("mutation", "Mutation".to_string()),
+    ];
+    // (type name, type description, fields), extensions merged into their type
+    let mut types: Vec<(String, String, Vec<Field>)> = Vec::new();
+
+    while parser.tokens.peek().is_some() {
+        let description = parser.description();
+        let keyword = match parser.tokens.next() {
+            Some(Token::Name(keyword)) => keyword,
+            _ => continue,
+        };
+        let keyword = if keyword == "extend" {
+            match parser.tokens.next() {
+                Some(Token::Name(keyword)) => keyword,
+                _ => continue,
+            }
+        } else {
+            keyword
+        };
+
+        match keyword.as_str() {
+            "schema" => {
+                parser.skip_header();
+                if parser.tokens.eat('{') {
+                    while let Some(Token::Name(operation)) = parser.tokens.next() {
+                        parser.tokens.eat(':');
+                        if let Some(Token::Name(name)) = parser.tokens.next() {
+                            if let Some(root) =
+                                roots.iter_mut().find(|(kind, _)| *kind == operation)
+                            {
+                                root.1 = name;
+                            }
+                        }
+                    }
+                }
+            }
+            "type" => {
+                let name = match parser.tokens.next() {
+                    Some(Token::Name(name)) => name,
+                    _ => continue,
+                };
+                parser.skip_header();
+                let fields = if parser.tokens.eat('{') {
+                    parser.fields()
+                } else {
+                    Vec::new()
+                };
+                match types.iter_mut().find(|(known, _, _)| *known == name) {
+                    Some((_, known_description, known_fields)) => {
+                        if known_description.is_empty() {
+                            *known_description = description;
+                        }
+                        known_fields.extend(fields);
+                    }
+                    None => types.push((name, description, fields)),
+                }
+            }
+            _ => {
+                parser.skip_header();
+                if parser.tokens.eat('{') {
+                    parser.tokens.skip_block();
+                }
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (_, root) in &roots {
+        let (name, description, fields) = match types.iter_mut().find(|(name, _, _)| name == root) {
+            Some(root_type) => root_type,
+            None => continue,
+        };
+        groups.push(ApiGroup {
+            name: name.clone(),
+            description: description.clone(),
+            base: String::new(),
+            endpoints: std::mem::take(fields)
+                .into_iter()
+                .map(|field| Endpoint {
+                    text: humanize(&field.name),
+                    description: field.description,
+                    verb: "POST".to_string(),
+                    path: GRAPHQL_PATH.to_string(),
+                    parameters: field.arguments,
+                    ..Default::default()
+                })
+                .collect(),
+        });
+    }
+
+    if groups.iter().all(|group| group.endpoints.is_empty()) {
+        return Err("GraphQL schema has no query or mutation fields".into());
+    }
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+struct Parser {
+    tokens: Cursor<Token>,
+}
+
+impl Parser {
+    fn description(&mut self) -> String {
+        self.tokens
+            .take(|token| match token {
+                Token::Description(text) => Some(text.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Skips `implements`, directives and union members up to the body, or
+    /// to the next definition when there is none
+    fn skip_header(&mut self) {
+        while let Some(token) = self.tokens.peek() {
+            match token {
+                Token::Punct('{') => return,
+                Token::Punct('(') => {
+                    self.tokens.advance();
+                    self.tokens.skip_until(')');
+                }
+                Token::Description(_) => return,
+                Token::Name(name) if DEFINITIONS.contains(&name.as_str()) => return,
+                _ => self.tokens.advance(),
+            }
+        }
+    }
+
+    /// Fields of a type body, up to its closing brace
+    fn fields(&mut self) -> Vec<Field> {
+        let mut fields = Vec::new();
+        loop {
+            let description = self.description();
+            let name = match self.tokens.next() {
+                Some(Token::Name(name)) => name,
+                _ => return fields,
+            };
+            let arguments = if self.tokens.eat('(') {
+                self.arguments()
+            } else {
+                Vec::new()
+            };
+            self.tokens.eat(':');
+            self.type_reference();
+            self.directives();
+            fields.push(Field {
+                name,
+                description,
+                arguments,
+            });
+        }
+    }
+
+    /// Arguments of a field, up to the closing parenthesis
+    fn arguments(&mut self) -> Vec<Parameter> {
+        let mut arguments = Vec::new();
+        loop {
+            let description = self.description();
+            let name = match self.tokens.next() {
+                Some(Token::Name(name)) => name,
+                _ => return arguments,
+            };
+            self.tokens.eat(':');
+            let non_null = self.type_reference();
+            let default = self.tokens.eat('=');
+            if default {
+                self.value();
+            }
+            self.directives();
+            arguments.push(Parameter {
+                name,
+                description,
+                required: non_null && !default,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Reads `Name`, `[Name!]!`... and returns whether the outer type is non-null
+    fn type_reference(&mut self) -> bool {
+        if self.tokens.eat('[') {
+            self.type_reference();
+            self.tokens.eat(']');
+        } else {
+            self.tokens.next();
+        }
+        self.tokens.eat('!')
+    }
+
+    fn value(&mut self) {
+        match self.tokens.next() {
+            Some(Token::Punct('[')) => self.tokens.skip_until(']'),
+            Some(Token::Punct('{')) => self.tokens.skip_until('}'),
+            _ => {}
+        }
+    }
+
+    fn directives(&mut self) {
+        while self.tokens.eat('@') {
+            self.tokens.next();
+            if self.tokens.eat('(') {
+                self.tokens.skip_until(')');
+            }
+        }
+    }
+}
+
+/// Names and values, punctuation and strings of a schema, without comments
+/// and commas. Strings are only kept when they describe what follows them,
+/// which is where the grammar allows them outside of default values.
+fn tokenize(input_content: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input_content.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                chars.next();
+                let text = if chars.next_if_eq(&'"').is_some() {
+                    if chars.next_if_eq(&'"').is_some() {
+                        block_string(&mut chars)?
+                    } else {
+                        // ""
+                        String::new()
+                    }
+                } else {
+                    string(&mut chars)?
+                };
+                tokens.push(Token::Description(text));
+            }
+            c if c.is_whitespace() || c == ',' => {
+                chars.next();
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '!' | '=' | '@' | '|' | '&' => {
+                chars.next();
+                tokens.push(Token::Punct(c));
+            }
+            _ => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || "_-+.$".contains(*c)) {
+                    name.push(c);
+                }
+                if name.is_empty() {
+                    chars.next();
+                } else {
+                    tokens.push(Token::Name(name));
+                }
+            }
+        }
+    }
+    Some(tokens)
+}
+
+/// Rest of a `"""` block string, its lines joined into one
+fn block_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut text = String::new();
+    loop {
+        match chars.next()? {
+            '"' if chars.next_if_eq(&'"').is_some() => {
+                if chars.next_if_eq(&'"').is_some() {
+                    break;
+                }
+                text.push_str("\"\"");
+            }
+            '\\' if chars.peek() == Some(&'"') => {
+                // \""" is an escaped delimiter
+                text.push('"');
+                chars.next();
+            }
+            c => text.push(c),
+        }
+    }
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Rest of a single-line `"` string
+fn string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut text = String::new();
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\n' => return None,
+            '\\' => match chars.next()? {
+                'n' | 't' | 'r' => text.push(' '),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    text.extend(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32));
+                }
+                escaped => text.push(escaped),
+            },
+            c => text.push(c),
+        }
+    }
+    Some(text.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+"""
+Library API
+"""
+schema {
+  query: RootQuery
+  mutation: RootMutation
+}
+
+scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimcc/date-time")
+
+enum Genre { FICTION, HISTORY }
+
+union SearchResult = Book | Author
+
+"Read-only operations"
+type RootQuery {
+  "Fetch a single book"
+  book(
+    "Book identifier"
+    id: ID!
+  ): Book
+  books(genre: Genre, first: Int! = 10, tags: [String!]!): [Book!]! @deprecated(reason: "Use search")
+  # Searching across books and authors
+  search(term: String!): [SearchResult!]!
+}
+
+type RootMutation {
+  """
+  Add a book
+  to the library
+  """
+  addBook(input: BookInput!, publishedAt: DateTime): Book
+}
+
+extend type RootQuery {
+  author(id: ID!): Author
+}
+
+input BookInput {
+  title: String!
+  genre: Genre = FICTION
+}
+
+type Book implements Node & Item @key(fields: "id") {
+  id: ID!
+  title: String!
+}
+"#;
+
+    #[test]
+    fn maps_root_fields_to_endpoints() {
+        assert!(detect(SCHEMA));
+        let catalog = import(SCHEMA).unwrap();
+
+        let groups: Vec<(&str, &str)> = catalog
+            .api_groups
+            .iter()
+            .map(|group| (group.name.as_str(), group.description.as_str()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("RootQuery", "Read-only operations"), ("RootMutation", ""),]
+        );
+
+        let queries = &catalog.api_groups[0].endpoints;
+        let texts: Vec<&str> = queries
+            .iter()
+            .map(|endpoint| endpoint.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["Book", "Books", "Search", "Author"]);
+        assert_eq!(queries[0].description, "Fetch a single book");
+        assert_eq!(queries[0].verb, "POST");
+        assert_eq!(queries[0].path, "/graphql");
+        assert_eq!(queries[0].parameters[0].description, "Book identifier");
+
+        let arguments: Vec<(&str, bool)> = queries[1]
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(
+            arguments,
+            vec![("genre", false), ("first", false), ("tags", true)]
+        );
+
+        let add_book = &catalog.api_groups[1].endpoints[0];
+        assert_eq!(add_book.text, "Add book");
+        assert_eq!(add_book.description, "Add a book to the library");
+        let arguments: Vec<(&str, bool)> = add_book
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(arguments, vec![("input", true), ("publishedAt", false)]);
+    }
+
+    #[test]
+    fn ignores_other_inputs() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert!(!detect(&input));
+        assert!(!detect("type User { id: ID! }"));
+    }
+}
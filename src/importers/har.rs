@@ -0,0 +1,343 @@
+use serde_yaml::Value;
+use std::error::Error;
+
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+/// Recorded files that are not API calls
+const STATIC_EXTENSIONS: &[&str] = &[
+    ".js", ".mjs", ".css", ".map", ".html", ".htm", ".png", ".jpg", ".jpeg", ".gif", ".svg",
+    ".ico", ".webp", ".woff", ".woff2", ".ttf", ".eot",
+];
+
+/// An endpoint and the parameters seen on each of its recorded calls
+struct Observed {
+    endpoint: Endpoint,
+    calls: usize,
+    /// Parameter name and the number of calls it was sent with
+    seen: Vec<(String, usize)>,
+}
+
+/// True for HTTP Archive captures exported by browsers
+pub fn detect(document: &Value) -> bool {
+    document
+        .get("log")
+        .and_then(|log| log.get("entries"))
+        .is_some_and(Value::is_sequence)
+}
+
+/// Derives endpoints from recorded traffic: one group per origin, numeric and
+/// UUID path segments collapsed into `{id}` placeholders, query and body
+/// fields as parameters, and repeated calls merged into one endpoint whose
+/// parameters are required when every call sent them
+pub fn import(document: &Value) -> Result<ApiCatalog, Box<dyn Error>> {
+    let entries = document
+        .get("log")
+        .and_then(|log| log.get("entries"))
+        .and_then(Value::as_sequence)
+        .ok_or("HAR file has no entries")?;
+
+    // (host, origin, calls) in order of first appearance
+    let mut origins: Vec<(String, String, Vec<Observed>)> = Vec::new();
+
+    for request in entries.iter().filter_map(|entry| entry.get("request")) {
+        let method = string(request, "method").to_uppercase();
+        let url = match url::Url::parse(&string(request, "url")) {
+            Ok(url) => url,
+            Err(_) => continue,
+        };
+        // Preflights and assets are not part of the API
+        if method.is_empty()
+            || method == "OPTIONS"
+            || STATIC_EXTENSIONS
+                .iter()
+                .any(|extension| url.path().to_lowercase().ends_with(extension))
+        {
+            continue;
+        }
+        let host = match url.host_str() {
+            Some(host) => host.to_string(),
+            None => continue,
+        };
+
+        let path = collapse_identifiers(url.path());
+        let mut names: Vec<(String, bool)> = path_placeholders(&path)
+            .into_iter()
+            .map(|name| (name.to_string(), true))
+            .collect();
+        for (name, _) in url.query_pairs() {
+            names.push((name.into_owned(), false));
+        }
+        names.extend(body_fields(request).into_iter().map(|name| (name, false)));
+
+        // Another scheme or port of the same host is another base URL
+        let origin = url.origin().ascii_serialization();
+        let origin_index = match origins.iter().position(|(_, known, _)| *known == origin) {
+            Some(index) => index,
+            None => {
+                origins.push((host, origin, Vec::new()));
+                origins.len() - 1
+            }
+        };
+        let calls = &mut origins[origin_index].2;
+        let call_index = match calls
+            .iter()
+            .position(|observed| observed.endpoint.verb == method && observed.endpoint.path == path)
+        {
+            Some(index) => index,
+            None => {
+                calls.push(Observed {
+                    endpoint: Endpoint {
+                        text: format!("{} {}", method, path),
+                        verb: method.clone(),
+                        path: path.clone(),
+                        ..Default::default()
+                    },
+                    calls: 0,
+                    seen: Vec::new(),
+                });
+                calls.len() - 1
+            }
+        };
+        let observed = &mut calls[call_index];
+
+        observed.calls += 1;
+        let mut counted: Vec<&str> = Vec::new();
+        for (name, in_path) in &names {
+            if counted.contains(&name.as_str()) {
+                continue;
+            }
+            counted.push(name.as_str());
+            match observed.seen.iter_mut().find(|(known, _)| known == name) {
+                Some((_, count)) => *count += 1,
+                None => {
+                    observed.seen.push((name.clone(), 1));
+                    observed.endpoint.parameters.push(Parameter {
+                        name: name.clone(),
+                        required: *in_path,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    // Groups are named after their host, or their origin when several
+    // origins share it
+    let hosts: Vec<String> = origins.iter().map(|(host, _, _)| host.clone()).collect();
+    let groups = origins
+        .into_iter()
+        .map(|(host, origin, calls)| ApiGroup {
+            name: if hosts.iter().filter(|known| **known == host).count() > 1 {
+                origin.clone()
+            } else {
+                host
+            },
+            description: String::new(),
+            base: origin,
+            endpoints: calls
+                .into_iter()
+                .map(|mut observed| {
+                    for parameter in &mut observed.endpoint.parameters {
+                        let count = observed
+                            .seen
+                            .iter()
+                            .find(|(name, _)| *name == parameter.name)
+                            .map_or(0, |(_, count)| *count);
+                        parameter.required |= count == observed.calls;
+                    }
+                    observed.endpoint
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+/// Replaces numeric, UUID and long hexadecimal segments with `{id}`,
+/// numbering further ones `{id2}`, `{id3}`...
+pub fn collapse_identifiers(path: &str) -> String {
+    let mut count = 0;
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            if is_identifier(segment) {
+                count += 1;
+                if count == 1 {
+                    "{id}".to_string()
+                } else {
+                    format!("{{id{}}}", count)
+                }
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+    let path = segments.join("/");
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path
+    }
+}
+
+fn is_identifier(segment: &str) -> bool {
+    if segment.is_empty() {
+        return false;
+    }
+    if segment.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+
+    // 8-4-4-4-12 UUIDs
+    let parts: Vec<&str> = segment.split('-').collect();
+    let uuid = parts.len() == 5
+        && parts
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(part, len)| part.len() == len && part.chars().all(|c| c.is_ascii_hexdigit()));
+
+    // Object ids and hashes, with at least one digit so words are kept
+    let hex = segment.len() >= 16
+        && segment.chars().all(|c| c.is_ascii_hexdigit())
+        && segment.chars().any(|c| c.is_ascii_digit());
+
+    uuid || hex
+}
+
+/// Top-level fields of a JSON body, or the names of form parameters
+fn body_fields(request: &Value) -> Vec<String> {
+    let post_data = match request.get("postData") {
+        Some(post_data) => post_data,
+        None => return Vec::new(),
+    };
+
+    if let Some(params) = post_data.get("params").and_then(Value::as_sequence) {
+        if !params.is_empty() {
+            return params
+                .iter()
+                .filter_map(|param| Some(param.get("name")?.as_str()?.to_string()))
+                .collect();
+        }
+    }
+
+    let mime_type = string(post_data, "mimeType");
+    if !mime_type.contains("json") {
+        return Vec::new();
+    }
+    match serde_yaml::from_str::<Value>(&string(post_data, "text")) {
+        Ok(Value::Mapping(fields)) => fields
+            .keys()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn string(value: &Value, field: &str) -> String {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPTURE: &str = r#"{"log": {"version": "1.2", "entries": [
+  {"request": {"method": "GET", "url": "https://api.example.com/users/42?expand=orders"}},
+  {"request": {"method": "GET", "url": "https://api.example.com/users/7"}},
+  {"request": {"method": "OPTIONS", "url": "https://api.example.com/users/7"}},
+  {"request": {"method": "GET", "url": "https://api.example.com/app.js"}},
+  {"request": {"method": "PUT", "url": "https://api.example.com/users/7/files/3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+    "postData": {"mimeType": "application/json", "text": "{\"name\": \"a.txt\", \"size\": 3}"}}},
+  {"request": {"method": "POST", "url": "http://localhost:8080/login",
+    "postData": {"mimeType": "application/x-www-form-urlencoded", "params": [{"name": "username"}, {"name": "password"}]}}}
+]}}"#;
+
+    #[test]
+    fn derives_deduplicated_endpoints_per_host() {
+        let document: Value = serde_yaml::from_str(CAPTURE).unwrap();
+        assert!(detect(&document));
+
+        let catalog = import(&document).unwrap();
+        let groups: Vec<(&str, &str)> = catalog
+            .api_groups
+            .iter()
+            .map(|group| (group.name.as_str(), group.base.as_str()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("api.example.com", "https://api.example.com"),
+                ("localhost", "http://localhost:8080"),
+            ]
+        );
+
+        let endpoints = &catalog.api_groups[0].endpoints;
+        let paths: Vec<&str> = endpoints
+            .iter()
+            .map(|endpoint| endpoint.text.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["GET /users/{id}", "PUT /users/{id}/files/{id2}"]
+        );
+
+        let parameters: Vec<(&str, bool)> = endpoints[0]
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(parameters, vec![("id", true), ("expand", false)]);
+
+        let body: Vec<&str> = endpoints[1]
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        assert_eq!(body, vec!["id", "id2", "name", "size"]);
+        assert!(catalog.api_groups[1].endpoints[0].parameters[0].required);
+    }
+
+    #[test]
+    fn separates_origins_of_the_same_host() {
+        let document: Value = serde_yaml::from_str(
+            r#"{"log": {"entries": [
+  {"request": {"method": "GET", "url": "http://api.example.com/status"}},
+  {"request": {"method": "GET", "url": "https://api.example.com:8443/admin/users"}},
+  {"request": {"method": "GET", "url": "https://api.example.com:8443/admin/users/3"}}
+]}}"#,
+        )
+        .unwrap();
+
+        let catalog = import(&document).unwrap();
+        let groups: Vec<(&str, &str, usize)> = catalog
+            .api_groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.as_str(),
+                    group.base.as_str(),
+                    group.endpoints.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("http://api.example.com", "http://api.example.com", 1),
+                (
+                    "https://api.example.com:8443",
+                    "https://api.example.com:8443",
+                    2
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use serde_yaml::Value;
+use std::error::Error;
+
+use super::humanize;
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+
+/// Group of endpoints listed before any section comment
+const DEFAULT_GROUP: &str = "Default";
+
+/// True for the `endpoints:` list of id/text entries used by our older
+/// configuration files, e.g. `samples/divess.yaml`
+pub fn detect(document: &Value) -> bool {
+    if document.get("api_groups").is_some() {
+        return false;
+    }
+    match document.get("endpoints").and_then(Value::as_sequence) {
+        Some(endpoints) => {
+            !endpoints.is_empty()
+                && endpoints.iter().all(|endpoint| {
+                    endpoint.is_mapping()
+                        && (endpoint.get("id").is_some() || endpoint.get("text").is_some())
+                })
+        }
+        None => false,
+    }
+}
+
+/// Converts each entry to an endpoint. Groups come from a `group` field or,
+/// failing that, from the `# Section` comments between entries. Verb and
+/// path are copied when present and left blank otherwise.
+pub fn import(input_content: &str, document: &Value) -> Result<ApiCatalog, Box<dyn Error>> {
+    let entries = document
+        .get("endpoints")
+        .and_then(Value::as_sequence)
+        .ok_or("Input has no endpoints list")?;
+
+    let sections =
+        section_comments(input_content).filter(|sections| sections.len() == entries.len());
+    let mut groups: Vec<ApiGroup> = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let group_name = match entry.get("group").and_then(Value::as_str) {
+            Some(group) => group.trim().to_string(),
+            None => sections
+                .as_ref()
+                .and_then(|sections| sections[index].clone())
+                .unwrap_or_else(|| DEFAULT_GROUP.to_string()),
+        };
+
+        let endpoint = Endpoint {
+            text: match (string(entry, "text"), string(entry, "id")) {
+                (text, _) if !text.is_empty() => text,
+                (_, id) => humanize(&id),
+            },
+            description: string(entry, "description"),
+            suggested_sentence: string(entry, "suggested_sentence"),
+            verb: string(entry, "verb").to_uppercase(),
+            path: string(entry, "path"),
+            base: entry
+                .get("base")
+                .and_then(Value::as_str)
+                .map(|base| base.trim().to_string())
+                .filter(|base| !base.is_empty()),
+            parameters: entry
+                .get("parameters")
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+                .filter_map(parameter)
+                .collect(),
+        };
+
+        match groups.iter_mut().find(|group| group.name == group_name) {
+            Some(group) => group.endpoints.push(endpoint),
+            None => groups.push(ApiGroup {
+                name: group_name,
+                description: String::new(),
+                base: string(document, "base"),
+                endpoints: vec![endpoint],
+            }),
+        }
+    }
+
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+/// The template has no default values, so a `default` is kept in the description
+fn parameter(value: &Value) -> Option<Parameter> {
+    let name = value.get("name")?.as_str()?.trim().to_string();
+    let mut description = string(value, "description");
+    if let Some(default) = value.get("default").filter(|default| !default.is_null()) {
+        let default = match default.as_str() {
+            Some(default) => default.to_string(),
+            None => serde_yaml::to_string(default).ok()?.trim().to_string(),
+        };
+        description = if description.is_empty() {
+            format!("Default: {}", default)
+        } else {
+            format!("{} (default: {})", description, default)
+        };
+    }
+
+    Some(Parameter {
+        name,
+        description,
+        required: value
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        alternatives: value
+            .get("alternatives")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+/// Section comment in effect for each top-level entry of the `endpoints:`
+/// block, in order. None when the block cannot be read line by line.
+fn section_comments(input_content: &str) -> Option<Vec<Option<String>>> {
+    let mut lines = input_content
+        .lines()
+        .skip_while(|line| line.trim_end() != "endpoints:");
+    lines.next()?;
+
+    let mut item_indent = None;
+    let mut section = None;
+    let mut sections = Vec::new();
+    for line in lines {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            if item_indent.is_none_or(|item_indent| indent <= item_indent) {
+                let comment = comment.trim();
+                if !comment.is_empty() {
+                    section = Some(comment.to_string());
+                }
+            }
+            continue;
+        }
+        if trimmed.starts_with('-') && item_indent.is_none_or(|item_indent| item_indent == indent) {
+            item_indent = Some(indent);
+            sections.push(section.clone());
+        } else if indent == 0 {
+            // Next top-level key, the endpoints block is over
+            break;
+        }
+    }
+
+    Some(sections)
+}
+
+fn string(value: &Value, field: &str) -> String {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import_sample(path: &str) -> ApiCatalog {
+        let input = std::fs::read_to_string(path).unwrap();
+        let document: Value = serde_yaml::from_str(&input).unwrap();
+        assert!(detect(&document));
+        import(&input, &document).unwrap()
+    }
+
+    #[test]
+    fn converts_divess_endpoints_into_one_group() {
+        let catalog = import_sample("samples/divess.yaml");
+        assert_eq!(catalog.api_groups.len(), 1);
+
+        let upload = &catalog.api_groups[0].endpoints[1];
+        assert_eq!(upload.text, "Upload document");
+        assert_eq!(upload.verb, "");
+        assert_eq!(upload.parameters[1].name, "idEvenement");
+        assert_eq!(
+            upload.parameters[1].alternatives,
+            vec!["event_id", "evenement_id"]
+        );
+    }
+
+    #[test]
+    fn groups_pickbazar_endpoints_by_section_comment() {
+        let catalog = import_sample("samples/pickbazar_endpoints.yaml");
+        let names: Vec<&str> = catalog
+            .api_groups
+            .iter()
+            .map(|group| group.name.as_str())
+            .collect();
+        assert_eq!(names[..3], ["Authentication & Users", "Products", "Orders"]);
+        assert_eq!(names.last(), Some(&"File Upload"));
+
+        let permission = &catalog.api_groups[0].endpoints[0].parameters[3];
+        assert_eq!(
+            permission.description,
+            "User permission level (default: CUSTOMER)"
+        );
+    }
+
+    #[test]
+    fn keeps_verbs_of_the_input() {
+        let catalog = import_sample("samples/gpecs.yaml");
+        assert_eq!(catalog.api_groups[0].endpoints[0].verb, "GET");
+    }
+
+    #[test]
+    fn ignores_grouped_and_free_text_inputs() {
+        let grouped: Value =
+            serde_yaml::from_str(&std::fs::read_to_string("samples/output.yaml").unwrap()).unwrap();
+        assert!(!detect(&grouped));
+        let text: Value =
+            serde_yaml::from_str(&std::fs::read_to_string("samples/input.txt").unwrap())
+                .unwrap_or(Value::Null);
+        assert!(!detect(&text));
+    }
+}
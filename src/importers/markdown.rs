@@ -0,0 +1,554 @@
+use std::error::Error;
+
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+const METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Heading, fenced code block or other non-blank line of a Markdown document
+enum Block<'a> {
+    Heading(usize, &'a str),
+    Code(Vec<&'a str>),
+    Text(&'a str),
+}
+
+/// An endpoint being read, until the next endpoint or enclosing heading
+struct Draft {
+    section: Option<String>,
+    /// Level of the heading the endpoint is documented under
+    heading_level: usize,
+    endpoint: Endpoint,
+    table_columns: Option<TableColumns>,
+}
+
+#[derive(Clone, Copy)]
+struct TableColumns {
+    name: usize,
+    required: Option<usize>,
+    description: Option<usize>,
+}
+
+/// True when the document has endpoint lines such as **GET** `/users/{id}`
+pub fn detect(input_content: &str) -> bool {
+    blocks(input_content).iter().any(|block| match block {
+        Block::Text(text) | Block::Heading(_, text) => verb_line(text).is_some(),
+        Block::Code(_) => false,
+    })
+}
+
+/// Reads endpoints from their verb lines: the heading above gives the text,
+/// the first paragraph the description, and path placeholders, parameter
+/// tables and JSON request bodies the parameters. The `Base URL:` note
+/// gives the base and `##` sections the groups.
+pub fn import(input_content: &str) -> Result<ApiCatalog, Box<dyn Error>> {
+    let mut title: Option<String> = None;
+    let mut base = String::new();
+    let mut section: Option<String> = None;
+    // Last heading not yet used as the text of an endpoint
+    let mut heading: Option<(usize, String)> = None;
+    // Last line before a code block, tells request bodies from responses
+    let mut label = String::new();
+    let mut current: Option<Draft> = None;
+    let mut drafts: Vec<Draft> = Vec::new();
+
+    for block in blocks(input_content) {
+        match block {
+            Block::Heading(level, text) => {
+                if current
+                    .as_ref()
+                    .is_some_and(|draft| level <= draft.heading_level)
+                {
+                    drafts.extend(current.take());
+                }
+                if let Some((verb, target)) = verb_line(text) {
+                    drafts.extend(current.take());
+                    current = Some(start_draft(verb, target, None, level, &section, &base));
+                    continue;
+                }
+                match level {
+                    1 if title.is_none() => title = Some(clean(text)),
+                    2 => section = Some(clean(text)),
+                    _ => {}
+                }
+                heading = Some((level, clean(text)));
+                label = text.to_lowercase();
+            }
+            Block::Text(text) => {
+                if base.is_empty() && text.to_lowercase().contains("base url") {
+                    if let Some(url) = first_url(text) {
+                        base = url;
+                    }
+                }
+
+                if let Some((verb, target)) = verb_line(text) {
+                    drafts.extend(current.take());
+                    let (level, text) = match heading.take() {
+                        Some((level, text)) => (level, Some(text)),
+                        None => (usize::MAX, None),
+                    };
+                    current = Some(start_draft(verb, target, text, level, &section, &base));
+                    continue;
+                }
+
+                if let Some(draft) = current.as_mut() {
+                    if text.starts_with('|') {
+                        table_row(draft, text);
+                    } else {
+                        draft.table_columns = None;
+                        if draft.endpoint.description.is_empty()
+                            && !text.starts_with("**")
+                            && text.chars().any(char::is_alphabetic)
+                        {
+                            draft.endpoint.description = clean(text);
+                        }
+                    }
+                }
+                label = text.to_lowercase();
+            }
+            Block::Code(lines) => {
+                let is_request = ["request", "body", "payload", "param"]
+                    .iter()
+                    .any(|word| label.contains(word))
+                    && !label.contains("response");
+                if let (Some(draft), true) = (current.as_mut(), is_request) {
+                    for parameter in json_fields(&lines) {
+                        add_parameter(&mut draft.endpoint.parameters, parameter);
+                    }
+                }
+                label.clear();
+            }
+        }
+    }
+    drafts.extend(current.take());
+
+    if drafts.is_empty() {
+        return Err("No endpoint lines found in the Markdown document".into());
+    }
+
+    // A single section, often just "Endpoints", is named after the document
+    let single_section = drafts
+        .iter()
+        .all(|draft| draft.section == drafts[0].section);
+    let mut groups: Vec<ApiGroup> = Vec::new();
+    for draft in drafts {
+        let name = match (&draft.section, &title) {
+            (_, Some(title)) if single_section => {
+                title.trim_end_matches("Documentation").trim().to_string()
+            }
+            (Some(section), _) => section.clone(),
+            (None, Some(title)) => title.clone(),
+            (None, None) => "Default".to_string(),
+        };
+        match groups.iter_mut().find(|group| group.name == name) {
+            Some(group) => group.endpoints.push(draft.endpoint),
+            None => groups.push(ApiGroup {
+                name,
+                description: String::new(),
+                base: base.clone(),
+                endpoints: vec![draft.endpoint],
+            }),
+        }
+    }
+
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+fn start_draft(
+    verb: &str,
+    target: &str,
+    heading: Option<String>,
+    heading_level: usize,
+    section: &Option<String>,
+    document_base: &str,
+) -> Draft {
+    // Full URLs carry their own base
+    let (endpoint_base, path) = match target.find("://") {
+        Some(scheme_end) => {
+            let path_start = target[scheme_end + 3..]
+                .find('/')
+                .map_or(target.len(), |index| scheme_end + 3 + index);
+            (
+                Some(target[..path_start].to_string()),
+                target[path_start..].to_string(),
+            )
+        }
+        None => (None, target.to_string()),
+    };
+    let path = express_placeholders(if path.is_empty() { "/" } else { &path });
+
+    let parameters = path_placeholders(&path)
+        .into_iter()
+        .map(|name| Parameter {
+            name: name.to_string(),
+            required: true,
+            ..Default::default()
+        })
+        .collect();
+
+    Draft {
+        section: section.clone(),
+        heading_level,
+        endpoint: Endpoint {
+            text: heading
+                .map(|heading| strip_numbering(&heading))
+                .filter(|text| !text.is_empty())
+                .unwrap_or_else(|| format!("{} {}", verb, path)),
+            verb: verb.to_string(),
+            path,
+            base: endpoint_base.filter(|endpoint_base| endpoint_base != document_base),
+            parameters,
+            ..Default::default()
+        },
+        table_columns: None,
+    }
+}
+
+/// Verb and path of lines like **GET** `/users/{id}`, `POST /users` or
+/// ### DELETE /users/:id
+fn verb_line(text: &str) -> Option<(&'static str, &str)> {
+    let stripped = text.trim_start_matches(['#', ' ']);
+    let mut tokens = stripped
+        .split_whitespace()
+        .map(|token| token.trim_matches(['*', '`', '_']))
+        .filter(|token| !token.is_empty());
+
+    let verb = tokens.next()?;
+    let verb = METHODS
+        .iter()
+        .find(|method| method.eq_ignore_ascii_case(verb) && verb.chars().all(char::is_uppercase))?;
+    let target = tokens.next()?;
+    if target.starts_with('/') || target.starts_with("http://") || target.starts_with("https://") {
+        Some((verb, target))
+    } else {
+        None
+    }
+}
+
+/// `:id` segments become `{id}` like the rest of the catalog
+fn express_placeholders(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if !name.is_empty() => format!("{{{}}}", name),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parameters from a table whose header names a name/parameter/field column
+fn table_row(draft: &mut Draft, text: &str) {
+    let cells: Vec<String> = text
+        .trim()
+        .trim_matches('|')
+        .split('|')
+        .map(clean)
+        .collect();
+
+    if cells
+        .iter()
+        .all(|cell| cell.chars().all(|c| c == '-' || c == ':' || c == ' '))
+    {
+        return;
+    }
+
+    let columns = match draft.table_columns {
+        Some(columns) => columns,
+        None => {
+            let lower: Vec<String> = cells.iter().map(|cell| cell.to_lowercase()).collect();
+            let find = |words: &[&str]| {
+                lower
+                    .iter()
+                    .position(|cell| words.iter().any(|word| cell.contains(word)))
+            };
+            if let Some(name) = find(&["name", "parameter", "field"]) {
+                draft.table_columns = Some(TableColumns {
+                    name,
+                    required: find(&["required"]),
+                    description: find(&["description"]),
+                });
+            }
+            return;
+        }
+    };
+
+    let name = match cells.get(columns.name) {
+        Some(name) if !name.is_empty() => name.clone(),
+        _ => return,
+    };
+    let required = columns
+        .required
+        .and_then(|index| cells.get(index))
+        .is_some_and(|cell| {
+            let cell = cell.to_lowercase();
+            cell.starts_with("yes") || cell == "true" || cell == "required" || cell == "✓"
+        });
+    add_parameter(
+        &mut draft.endpoint.parameters,
+        Parameter {
+            name,
+            description: columns
+                .description
+                .and_then(|index| cells.get(index))
+                .cloned()
+                .unwrap_or_default(),
+            required,
+            alternatives: Vec::new(),
+        },
+    );
+}
+
+/// Top-level fields of a JSON example, which may carry `// comment`
+/// descriptions; fields are required unless their comment says Optional
+fn json_fields(lines: &[&str]) -> Vec<Parameter> {
+    let mut fields = Vec::new();
+    let mut depth = 0i32;
+
+    for line in lines {
+        let (code, comment) = split_comment(line);
+        let trimmed = code.trim();
+
+        if depth == 1 && trimmed.starts_with('"') {
+            if let Some(end) = trimmed[1..].find('"') {
+                let name = &trimmed[1..end + 1];
+                if trimmed[end + 2..].trim_start().starts_with(':') {
+                    let (required, description) = match comment
+                        .strip_prefix("Optional:")
+                        .or_else(|| comment.strip_prefix("optional:"))
+                    {
+                        Some(rest) => (false, rest.trim()),
+                        None => (!comment.to_lowercase().starts_with("optional"), comment),
+                    };
+                    fields.push(Parameter {
+                        name: name.to_string(),
+                        description: description.to_string(),
+                        required,
+                        alternatives: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        let mut in_string = false;
+        let mut escaped = false;
+        for c in trimmed.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '{' | '[' if !in_string => depth += 1,
+                '}' | ']' if !in_string => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    fields
+}
+
+/// The code of a JSON example line and its `//` comment, which cannot start
+/// within a string such as `"https://cdn.example.com"`
+fn split_comment(line: &str) -> (&str, &str) {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut previous_slash = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '/' if !in_string && previous_slash => {
+                return (&line[..index - 1], line[index + 1..].trim());
+            }
+            _ => {}
+        }
+        previous_slash = c == '/' && !in_string;
+    }
+    (line, "")
+}
+
+/// Adds a parameter, completing the description of one already known
+fn add_parameter(parameters: &mut Vec<Parameter>, parameter: Parameter) {
+    match parameters
+        .iter_mut()
+        .find(|existing| existing.name == parameter.name)
+    {
+        Some(existing) => {
+            if existing.description.is_empty() {
+                existing.description = parameter.description;
+            }
+        }
+        None => parameters.push(parameter),
+    }
+}
+
+fn blocks(input_content: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in input_content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(Block::Code(lines)),
+                None => code = Some(Vec::new()),
+            }
+            continue;
+        }
+        if let Some(lines) = code.as_mut() {
+            lines.push(line);
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            blocks.push(Block::Heading(level, trimmed[level..].trim()));
+        } else {
+            blocks.push(Block::Text(trimmed));
+        }
+    }
+    blocks
+}
+
+fn first_url(text: &str) -> Option<String> {
+    let start = text.find("http://").or_else(|| text.find("https://"))?;
+    let url: String = text[start..]
+        .chars()
+        .take_while(|c| !c.is_whitespace() && !matches!(c, '`' | ')' | '>' | '"' | '\''))
+        .collect();
+    Some(url.trim_end_matches(['/', '.', ',']).to_string())
+}
+
+/// Text without Markdown emphasis and code marks
+fn clean(text: &str) -> String {
+    text.replace(['*', '`'], "").trim().to_string()
+}
+
+/// "3. Prepare Swap Transaction" becomes "Prepare Swap Transaction"
+fn strip_numbering(heading: &str) -> String {
+    let rest = heading.trim_start_matches(|c: char| c.is_ascii_digit());
+    match rest.strip_prefix(['.', ')']) {
+        Some(rest) if rest.len() < heading.len() => rest.trim().to_string(),
+        _ => heading.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_solana_documentation() {
+        let input = std::fs::read_to_string("samples/solana_api_documentation.md").unwrap();
+        assert!(detect(&input));
+
+        let catalog = import(&input).unwrap();
+        assert_eq!(catalog.api_groups.len(), 1);
+        let group = &catalog.api_groups[0];
+        assert_eq!(group.name, "Solana API");
+        assert_eq!(group.base, "http://localhost:8000");
+        assert_eq!(group.endpoints.len(), 10);
+
+        let health = &group.endpoints[0];
+        assert_eq!(health.text, "Health Check");
+        assert_eq!(
+            (health.verb.as_str(), health.path.as_str()),
+            ("GET", "/solana/health")
+        );
+        assert_eq!(health.description, "Check if the API service is running.");
+        assert!(health.parameters.is_empty());
+
+        let history = &group.endpoints[8];
+        let parameters: Vec<(&str, bool, &str)> = history
+            .parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.as_str(),
+                    parameter.required,
+                    parameter.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![
+                ("pubkey", true, "Wallet public key"),
+                ("limit", false, "number of transactions (default: 50)"),
+                ("before", false, "signature to paginate before"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_sections_tables_and_path_placeholders() {
+        let input = "\
+# Shop API
+
+## Users
+
+### GET /users/:id
+
+Fetch one user.
+
+| Parameter | Type | Required | Description |
+|-----------|------|----------|-------------|
+| `id` | string | yes | User identifier |
+| `expand` | string | no | Related objects |
+
+## Orders
+
+`DELETE https://orders.example.com/orders/{orderId}`
+";
+        assert!(detect(input));
+        let catalog = import(input).unwrap();
+
+        let names: Vec<&str> = catalog
+            .api_groups
+            .iter()
+            .map(|group| group.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Users", "Orders"]);
+
+        let user = &catalog.api_groups[0].endpoints[0];
+        assert_eq!(user.text, "GET /users/{id}");
+        assert_eq!(user.path, "/users/{id}");
+        assert_eq!(user.parameters[0].description, "User identifier");
+        assert!(!user.parameters[1].required);
+
+        let order = &catalog.api_groups[1].endpoints[0];
+        assert_eq!(order.base.as_deref(), Some("https://orders.example.com"));
+        assert_eq!(order.parameters[0].name, "orderId");
+    }
+
+    #[test]
+    fn keeps_urls_in_json_values() {
+        let lines = [
+            "{",
+            r#"  "avatar": "https://cdn.example.com/a.png", // Optional: Picture URL"#,
+            r#"  "bio": "likes { and \"//\"","#,
+            r#"  "name": "Ann" // Display name"#,
+            "}",
+        ];
+        let fields: Vec<(String, bool, String)> = json_fields(&lines)
+            .into_iter()
+            .map(|field| (field.name, field.required, field.description))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("avatar".into(), false, "Picture URL".into()),
+                ("bio".into(), true, String::new()),
+                ("name".into(), true, "Display name".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_text_without_endpoint_lines() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert!(!detect(&input));
+    }
+}
@@ -0,0 +1,135 @@
+use serde_yaml::Value;
+use std::error::Error;
+
+use crate::catalog::ApiCatalog;
+
+pub mod curl;
+pub mod graphql;
+pub mod har;
+pub mod legacy;
+pub mod markdown;
+pub mod openapi;
+pub mod postman;
+pub mod protobuf;
+mod tokens;
+
+/// A catalog converted natively from a known input format
+pub struct ImportedCatalog {
+    /// Name of the detected format, reported to the client
+    pub format: &'static str,
+    pub catalog: ApiCatalog,
+    /// Fields the LLM is asked for when the input leaves them blank
+    pub enrich: &'static [&'static str],
+}
+
+/// Converts inputs whose mapping to `api_groups` is mechanical, without the LLM.
+/// Returns None when the input is in no known format.
+pub fn import(input_content: &str) -> Option<Result<ImportedCatalog, Box<dyn Error>>> {
+    if let Ok(document) = serde_yaml::from_str::<Value>(input_content) {
+        if openapi::detect(&document) {
+            return Some(openapi::import(&document).map(|catalog| ImportedCatalog {
+                format: "openapi",
+                catalog,
+                enrich: &["suggested_sentence", "alternatives"],
+            }));
+        }
+
+        if postman::detect(&document) {
+            return Some(postman::import(&document).map(|catalog| ImportedCatalog {
+                format: "postman",
+                catalog,
+                enrich: &["suggested_sentence", "alternatives"],
+            }));
+        }
+
+        if har::detect(&document) {
+            return Some(har::import(&document).map(|catalog| ImportedCatalog {
+                format: "har",
+                catalog,
+                enrich: &["description", "suggested_sentence", "alternatives"],
+            }));
+        }
+
+        if legacy::detect(&document) {
+            return Some(
+                legacy::import(input_content, &document).map(|catalog| ImportedCatalog {
+                    format: "legacy-endpoints",
+                    catalog,
+                    enrich: &["verb", "path"],
+                }),
+            );
+        }
+    }
+
+    if markdown::detect(input_content) {
+        return Some(
+            markdown::import(input_content).map(|catalog| ImportedCatalog {
+                format: "markdown",
+                catalog,
+                enrich: &["suggested_sentence", "alternatives"],
+            }),
+        );
+    }
+
+    if graphql::detect(input_content) {
+        return Some(
+            graphql::import(input_content).map(|catalog| ImportedCatalog {
+                format: "graphql",
+                catalog,
+                enrich: &["suggested_sentence", "alternatives"],
+            }),
+        );
+    }
+
+    if protobuf::detect(input_content) {
+        // Text that merely looks like a proto file is left to the LLM
+        if let Ok(catalog) = protobuf::import(input_content) {
+            return Some(Ok(ImportedCatalog {
+                format: "protobuf",
+                catalog,
+                enrich: &["suggested_sentence", "alternatives"],
+            }));
+        }
+    }
+
+    if curl::detect(input_content) {
+        return Some(curl::import(input_content).map(|catalog| ImportedCatalog {
+            format: "curl",
+            catalog,
+            enrich: &["description", "suggested_sentence"],
+        }));
+    }
+
+    None
+}
+
+/// `getUserById` and `get_user_by_id` both become "Get user by id"
+pub fn humanize(identifier: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in identifier.chars() {
+        if c == '_' || c == '-' || c == ' ' || c == '.' {
+            previous_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    let sentence = words.join(" ");
+    let mut chars = sentence.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => sentence,
+    }
+}
@@ -0,0 +1,496 @@
+use serde_yaml::{Mapping, Value};
+use std::error::Error;
+
+use super::humanize;
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Nesting limit when following `$ref`s, guards against reference cycles
+const MAX_REF_DEPTH: usize = 16;
+
+/// True for OpenAPI 3 and Swagger 2 documents
+pub fn detect(document: &Value) -> bool {
+    (document.get("openapi").is_some() || document.get("swagger").is_some())
+        && document.get("paths").is_some_and(Value::is_mapping)
+}
+
+/// Maps tags to groups, operations to endpoints, parameters and request
+/// bodies to parameters, and servers (or host and basePath) to base URLs
+pub fn import(document: &Value) -> Result<ApiCatalog, Box<dyn Error>> {
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_mapping)
+        .ok_or("OpenAPI document has no paths")?;
+    let (base, base_prefix) = split_base(&document_base(document));
+
+    // Declared tags come first, in their declared order
+    let mut groups: Vec<ApiGroup> = document
+        .get("tags")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| {
+            Some(ApiGroup {
+                name: tag.get("name")?.as_str()?.to_string(),
+                description: string(tag, "description"),
+                base: base.clone(),
+                endpoints: Vec::new(),
+            })
+        })
+        .collect();
+    let default_group = document
+        .get("info")
+        .and_then(|info| info.get("title"))
+        .and_then(Value::as_str)
+        .unwrap_or("Default")
+        .to_string();
+
+    for (path, path_item) in paths {
+        let path = match path.as_str() {
+            Some(path) => path,
+            None => continue,
+        };
+        let path_item = resolve(document, path_item);
+        let path_servers = servers_base(path_item);
+
+        for (method, operation) in path_item.as_mapping().into_iter().flatten() {
+            let method = match method.as_str() {
+                Some(method) if METHODS.contains(&method) => method,
+                _ => continue,
+            };
+
+            let mut parameters = operation_parameters(document, path_item, operation);
+            parameters.extend(request_body_parameters(document, operation));
+
+            let endpoint_base = servers_base(operation).or_else(|| path_servers.clone());
+            let (endpoint_base, endpoint_path) = match endpoint_base {
+                Some(server) => {
+                    let (server_base, prefix) = split_base(&server);
+                    (Some(server_base), format!("{}{}", prefix, path))
+                }
+                None => (None, format!("{}{}", base_prefix, path)),
+            };
+
+            let endpoint = Endpoint {
+                text: operation_text(operation, method, path),
+                description: {
+                    let description = string(operation, "description");
+                    if description.is_empty() {
+                        string(operation, "summary")
+                    } else {
+                        description
+                    }
+                },
+                verb: method.to_uppercase(),
+                path: endpoint_path,
+                base: endpoint_base.filter(|server_base| *server_base != base),
+                parameters,
+                ..Default::default()
+            };
+
+            let tag = operation
+                .get("tags")
+                .and_then(|tags| tags.get(0))
+                .and_then(Value::as_str)
+                .unwrap_or(&default_group);
+            match groups.iter_mut().find(|group| group.name == tag) {
+                Some(group) => group.endpoints.push(endpoint),
+                None => groups.push(ApiGroup {
+                    name: tag.to_string(),
+                    description: String::new(),
+                    base: base.clone(),
+                    endpoints: vec![endpoint],
+                }),
+            }
+        }
+    }
+
+    groups.retain(|group| !group.endpoints.is_empty());
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+/// Server URL of the document: the first OpenAPI 3 server, or the Swagger 2
+/// scheme, host and basePath
+fn document_base(document: &Value) -> String {
+    if let Some(base) = servers_base(document) {
+        return base;
+    }
+
+    let base_path = string(document, "basePath");
+    match document.get("host").and_then(Value::as_str) {
+        Some(host) => {
+            let scheme = document
+                .get("schemes")
+                .and_then(|schemes| schemes.get(0))
+                .and_then(Value::as_str)
+                .unwrap_or("https");
+            format!("{}://{}{}", scheme, host, base_path)
+        }
+        None => base_path,
+    }
+}
+
+/// First entry of a `servers` list with its variables set to their defaults
+fn servers_base(value: &Value) -> Option<String> {
+    let server = value.get("servers")?.get(0)?;
+    let mut url = server.get("url")?.as_str()?.to_string();
+    for (name, variable) in server
+        .get("variables")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+    {
+        if let (Some(name), Some(default)) = (name.as_str(), variable.get("default")) {
+            let default = default.as_str().map(str::to_string).unwrap_or_else(|| {
+                serde_yaml::to_string(default)
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            });
+            url = url.replace(&format!("{{{}}}", name), &default);
+        }
+    }
+    Some(url)
+}
+
+/// Splits a server URL into the base of the catalog and a path prefix.
+/// Relative servers such as `/api/v1` have no base, only a prefix.
+fn split_base(server: &str) -> (String, String) {
+    let server = server.trim().trim_end_matches('/');
+    if server.contains("://") {
+        (server.to_string(), String::new())
+    } else {
+        (String::new(), server.to_string())
+    }
+}
+
+/// Path item and operation parameters, the latter overriding the former.
+/// Header and cookie parameters are transport details and are left out.
+fn operation_parameters(document: &Value, path_item: &Value, operation: &Value) -> Vec<Parameter> {
+    let mut parameters: Vec<(String, Parameter)> = Vec::new();
+
+    for parameter in [path_item, operation]
+        .iter()
+        .filter_map(|item| item.get("parameters").and_then(Value::as_sequence))
+        .flatten()
+    {
+        let parameter = resolve(document, parameter);
+        let location = string(parameter, "in");
+        let name = string(parameter, "name");
+        if name.is_empty() || location == "header" || location == "cookie" {
+            continue;
+        }
+
+        // Swagger 2 request bodies are a parameter with a schema
+        if location == "body" {
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            for property in schema_properties(document, schema) {
+                upsert(&mut parameters, "body", property);
+            }
+            continue;
+        }
+
+        let schema = parameter
+            .get("schema")
+            .map(|schema| resolve(document, schema));
+        let mut description = string(parameter, "description");
+        if description.is_empty() {
+            description = schema
+                .map(|schema| string(schema, "description"))
+                .unwrap_or_default();
+        }
+        upsert(
+            &mut parameters,
+            &location,
+            Parameter {
+                name,
+                description,
+                required: location == "path"
+                    || parameter
+                        .get("required")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                alternatives: Vec::new(),
+            },
+        );
+    }
+
+    parameters
+        .into_iter()
+        .map(|(_, parameter)| parameter)
+        .collect()
+}
+
+/// Replaces a parameter with the same name and location, or appends it
+fn upsert(parameters: &mut Vec<(String, Parameter)>, location: &str, parameter: Parameter) {
+    match parameters.iter_mut().find(|(existing_location, existing)| {
+        existing_location == location && existing.name == parameter.name
+    }) {
+        Some(existing) => existing.1 = parameter,
+        None => parameters.push((location.to_string(), parameter)),
+    }
+}
+
+/// Properties of the OpenAPI 3 request body, JSON content first
+fn request_body_parameters(document: &Value, operation: &Value) -> Vec<Parameter> {
+    let body = match operation.get("requestBody") {
+        Some(body) => resolve(document, body),
+        None => return Vec::new(),
+    };
+    let content = match body.get("content").and_then(Value::as_mapping) {
+        Some(content) => content,
+        None => return Vec::new(),
+    };
+
+    let media = content
+        .iter()
+        .find(|(media_type, _)| {
+            media_type
+                .as_str()
+                .is_some_and(|media_type| media_type.contains("json"))
+        })
+        .or_else(|| content.iter().next())
+        .map(|(_, media)| media);
+    match media.and_then(|media| media.get("schema")) {
+        Some(schema) => schema_properties(document, schema),
+        None => Vec::new(),
+    }
+}
+
+/// Top-level properties of an object schema, following `$ref` and `allOf`
+fn schema_properties(document: &Value, schema: &Value) -> Vec<Parameter> {
+    let mut properties = Mapping::new();
+    let mut required = Vec::new();
+    collect_properties(document, schema, &mut properties, &mut required, 0);
+
+    properties
+        .iter()
+        .filter_map(|(name, property)| {
+            let name = name.as_str()?.to_string();
+            let property = resolve(document, property);
+            Some(Parameter {
+                required: required.contains(&name),
+                description: string(property, "description"),
+                name,
+                alternatives: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn collect_properties(
+    document: &Value,
+    schema: &Value,
+    properties: &mut Mapping,
+    required: &mut Vec<String>,
+    depth: usize,
+) {
+    if depth > MAX_REF_DEPTH {
+        return;
+    }
+    let schema = resolve(document, schema);
+
+    for part in schema
+        .get("allOf")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+    {
+        collect_properties(document, part, properties, required, depth + 1);
+    }
+    if let Some(own) = schema.get("properties").and_then(Value::as_mapping) {
+        for (name, property) in own {
+            properties.insert(name.clone(), property.clone());
+        }
+    }
+    required.extend(
+        schema
+            .get("required")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string),
+    );
+}
+
+/// Follows local `$ref`s such as `#/components/schemas/User`
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_REF_DEPTH {
+        let reference = match current.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference,
+            None => return current,
+        };
+        let pointer = match reference.strip_prefix("#/") {
+            Some(pointer) => pointer,
+            None => return current,
+        };
+        let mut target = document;
+        for segment in pointer.split('/') {
+            let segment = segment.replace("~1", "/").replace("~0", "~");
+            target = match target.get(segment.as_str()) {
+                Some(next) => next,
+                None => return current,
+            };
+        }
+        current = target;
+    }
+    current
+}
+
+/// Summary, else a readable operationId, else the verb and path
+fn operation_text(operation: &Value, method: &str, path: &str) -> String {
+    let summary = string(operation, "summary");
+    if !summary.is_empty() {
+        return summary.trim_end_matches('.').to_string();
+    }
+    match operation.get("operationId").and_then(Value::as_str) {
+        Some(operation_id) => humanize(operation_id),
+        None => format!("{} {}", method.to_uppercase(), path),
+    }
+}
+
+fn string(value: &Value, field: &str) -> String {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Petstore
+servers:
+  - url: https://{region}.petstore.example.com/v1
+    variables:
+      region:
+        default: eu
+tags:
+  - name: Pets
+    description: Everything about pets
+paths:
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        description: Pet identifier
+        schema:
+          type: string
+    get:
+      tags: [Pets]
+      summary: Get a pet.
+      parameters:
+        - name: X-Request-Id
+          in: header
+        - $ref: "#/components/parameters/Expand"
+      responses: {}
+    put:
+      tags: [Pets]
+      operationId: updatePetById
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses: {}
+  /health:
+    get:
+      responses: {}
+components:
+  parameters:
+    Expand:
+      name: expand
+      in: query
+      description: Related objects to include
+  schemas:
+    Pet:
+      allOf:
+        - $ref: "#/components/schemas/NewPet"
+        - properties:
+            tag:
+              type: string
+    NewPet:
+      required: [name]
+      properties:
+        name:
+          type: string
+          description: Pet name
+"##;
+
+    #[test]
+    fn maps_openapi_3_operations_to_groups() {
+        let document: Value = serde_yaml::from_str(PETSTORE).unwrap();
+        assert!(detect(&document));
+
+        let catalog = import(&document).unwrap();
+        let names: Vec<&str> = catalog
+            .api_groups
+            .iter()
+            .map(|group| group.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Pets", "Petstore"]);
+
+        let pets = &catalog.api_groups[0];
+        assert_eq!(pets.base, "https://eu.petstore.example.com/v1");
+        assert_eq!(pets.description, "Everything about pets");
+
+        let get = &pets.endpoints[0];
+        assert_eq!(get.text, "Get a pet");
+        assert_eq!(
+            (get.verb.as_str(), get.path.as_str()),
+            ("GET", "/pets/{petId}")
+        );
+        let parameters: Vec<(&str, bool)> = get
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(parameters, vec![("petId", true), ("expand", false)]);
+
+        let put = &pets.endpoints[1];
+        assert_eq!(put.text, "Update pet by id");
+        let parameters: Vec<(&str, bool)> = put
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![("petId", true), ("name", true), ("tag", false)]
+        );
+        assert_eq!(catalog.api_groups[1].endpoints[0].text, "GET /health");
+    }
+
+    #[test]
+    fn maps_swagger_2_host_and_body_parameters() {
+        let document: Value = serde_yaml::from_str(
+            r#"{"swagger": "2.0", "host": "api.example.com", "basePath": "/v2", "schemes": ["http"],
+                "paths": {"/users": {"post": {"tags": ["Users"], "summary": "Create user",
+                  "parameters": [{"in": "body", "name": "body", "schema": {"required": ["email"],
+                    "properties": {"email": {"type": "string"}, "age": {"type": "integer"}}}}]}}}}"#,
+        )
+        .unwrap();
+        assert!(detect(&document));
+
+        let catalog = import(&document).unwrap();
+        let group = &catalog.api_groups[0];
+        assert_eq!(group.base, "http://api.example.com/v2");
+        let parameters: Vec<(&str, bool)> = group.endpoints[0]
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), parameter.required))
+            .collect();
+        assert_eq!(parameters, vec![("email", true), ("age", false)]);
+    }
+}
@@ -0,0 +1,454 @@
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+use crate::catalog_validator::path_placeholders;
+
+/// True for Postman v2.0/v2.1 collections
+pub fn detect(document: &Value) -> bool {
+    let schema = document
+        .get("info")
+        .and_then(|info| info.get("schema"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    schema.contains("getpostman.com") && document.get("item").is_some_and(Value::is_sequence)
+}
+
+/// Maps folders to groups and requests to endpoints. Collection variables
+/// used as the host, like `{{baseUrl}}`, become the base URL; path
+/// variables, query parameters and body fields become parameters.
+pub fn import(document: &Value) -> Result<ApiCatalog, Box<dyn Error>> {
+    let items = document
+        .get("item")
+        .and_then(Value::as_sequence)
+        .ok_or("Postman collection has no items")?;
+    let variables: HashMap<String, String> = document
+        .get("variable")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(|variable| {
+            Some((
+                variable.get("key")?.as_str()?.to_string(),
+                scalar(variable.get("value")?),
+            ))
+        })
+        .collect();
+    let collection_name = document
+        .get("info")
+        .and_then(|info| info.get("name"))
+        .and_then(Value::as_str)
+        .unwrap_or("Default")
+        .to_string();
+
+    let mut groups: Vec<ApiGroup> = Vec::new();
+    collect_items(items, None, &collection_name, &variables, &mut groups);
+
+    // The base most endpoints of a group share becomes the group's base
+    for group in &mut groups {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for endpoint in &group.endpoints {
+            let base = endpoint.base.clone().unwrap_or_default();
+            match counts.iter_mut().find(|(known, _)| *known == base) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((base, 1)),
+            }
+        }
+        group.base = counts
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(base, _)| base.clone())
+            .unwrap_or_default();
+        for endpoint in &mut group.endpoints {
+            if endpoint.base.as_deref().unwrap_or_default() == group.base {
+                endpoint.base = None;
+            }
+        }
+    }
+
+    groups.retain(|group| !group.endpoints.is_empty());
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+/// Walks folders depth first; nested folders are named "Parent / Child"
+fn collect_items(
+    items: &[Value],
+    folder: Option<(&str, String)>,
+    collection_name: &str,
+    variables: &HashMap<String, String>,
+    groups: &mut Vec<ApiGroup>,
+) {
+    for item in items {
+        let name = item
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim();
+
+        if let Some(children) = item.get("item").and_then(Value::as_sequence) {
+            let group_name = match &folder {
+                Some((parent, _)) => format!("{} / {}", parent, name),
+                None => name.to_string(),
+            };
+            collect_items(
+                children,
+                Some((group_name.as_str(), description(item))),
+                collection_name,
+                variables,
+                groups,
+            );
+            continue;
+        }
+
+        let request = match item.get("request") {
+            Some(request) => request,
+            None => continue,
+        };
+        let endpoint = request_endpoint(name, request, variables);
+
+        let (group_name, group_description) = match &folder {
+            Some((name, description)) => (name.to_string(), description.clone()),
+            None => (collection_name.to_string(), String::new()),
+        };
+        match groups.iter_mut().find(|group| group.name == group_name) {
+            Some(group) => group.endpoints.push(endpoint),
+            None => groups.push(ApiGroup {
+                name: group_name,
+                description: group_description,
+                base: String::new(),
+                endpoints: vec![endpoint],
+            }),
+        }
+    }
+}
+
+fn request_endpoint(name: &str, request: &Value, variables: &HashMap<String, String>) -> Endpoint {
+    // A request can be a bare URL string, which means GET
+    let (method, url) = match request.as_str() {
+        Some(url) => ("GET".to_string(), Value::from(url)),
+        None => (
+            request
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or("GET")
+                .to_uppercase(),
+            request.get("url").cloned().unwrap_or(Value::Null),
+        ),
+    };
+
+    let (base, path) = split_url(&url, variables);
+    let mut parameters: Vec<Parameter> = Vec::new();
+
+    // Path variables, from `:id` segments and unresolved `{{id}}` ones
+    for placeholder in path_placeholders(&path) {
+        let description = url
+            .get("variable")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .find(|variable| variable.get("key").and_then(Value::as_str) == Some(placeholder))
+            .map(description)
+            .unwrap_or_default();
+        add_parameter(&mut parameters, placeholder, description, true);
+    }
+
+    for query in url
+        .get("query")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !disabled(entry))
+    {
+        if let Some(key) = query.get("key").and_then(Value::as_str) {
+            add_parameter(&mut parameters, key, description(query), false);
+        }
+    }
+
+    if let Some(body) = request.get("body") {
+        for (key, field_description) in body_fields(body) {
+            add_parameter(&mut parameters, &key, field_description, true);
+        }
+    }
+
+    let text = if name.is_empty() {
+        format!("{} {}", method, path)
+    } else {
+        name.to_string()
+    };
+    Endpoint {
+        text,
+        description: description(request),
+        verb: method,
+        path,
+        base: Some(base),
+        parameters,
+        ..Default::default()
+    }
+}
+
+/// Base URL and path of a request URL, given as a string or as an object
+/// with `raw`, `protocol`, `host` and `path`
+fn split_url(url: &Value, variables: &HashMap<String, String>) -> (String, String) {
+    let raw = match url.as_str() {
+        Some(raw) => raw.to_string(),
+        None => match (url.get("host"), url.get("path")) {
+            (Some(host), path) => {
+                let host = join(host, ".");
+                let path = path.map(|path| join(path, "/")).unwrap_or_default();
+                let protocol = url
+                    .get("protocol")
+                    .and_then(Value::as_str)
+                    .map(|protocol| format!("{}://", protocol))
+                    .unwrap_or_default();
+                format!("{}{}/{}", protocol, host, path.trim_start_matches('/'))
+            }
+            (None, _) => url
+                .get("raw")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        },
+    };
+    let raw = raw.split(['?', '#']).next().unwrap_or_default();
+
+    // The host is everything before the first single slash
+    let after_scheme = raw.find("://").map_or(0, |index| index + 3);
+    let path_start = raw[after_scheme..]
+        .find('/')
+        .map_or(raw.len(), |index| after_scheme + index);
+    let host = substitute(&raw[..path_start], variables);
+    let path = substitute(&raw[path_start..], variables);
+
+    let base = if host.is_empty() || host.contains("://") {
+        host
+    } else {
+        format!("https://{}", host)
+    };
+    let path = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if !name.is_empty() => format!("{{{}}}", name),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let path = if path.is_empty() {
+        "/".to_string()
+    } else {
+        path
+    };
+
+    (base.trim_end_matches('/').to_string(), path)
+}
+
+/// Replaces known `{{variables}}`, unknown ones become `{placeholders}`
+fn substitute(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start..].find("}}") {
+            Some(end) => start + end,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 2..end].trim();
+        match variables.get(name) {
+            Some(value) if !value.is_empty() => result.push_str(value),
+            _ => result.push_str(&format!("{{{}}}", name)),
+        }
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Fields of a raw JSON, urlencoded or form-data body
+fn body_fields(body: &Value) -> Vec<(String, String)> {
+    let mode = body.get("mode").and_then(Value::as_str).unwrap_or_default();
+    match mode {
+        "urlencoded" | "formdata" => body
+            .get(mode)
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter(|field| !disabled(field))
+            .filter_map(|field| Some((field.get("key")?.as_str()?.to_string(), description(field))))
+            .collect(),
+        "raw" => {
+            let raw = body.get("raw").and_then(Value::as_str).unwrap_or_default();
+            // Unquoted {{variables}} are not JSON, as {variables} they still
+            // parse as YAML, which also keeps the order of the fields
+            let raw = substitute(raw, &HashMap::new());
+            match serde_yaml::from_str::<Value>(&raw) {
+                Ok(Value::Mapping(fields)) => fields
+                    .keys()
+                    .filter_map(Value::as_str)
+                    .map(|key| (key.to_string(), String::new()))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn add_parameter(parameters: &mut Vec<Parameter>, name: &str, description: String, required: bool) {
+    if parameters.iter().any(|parameter| parameter.name == name) {
+        return;
+    }
+    parameters.push(Parameter {
+        name: name.to_string(),
+        description,
+        required,
+        alternatives: Vec::new(),
+    });
+}
+
+/// Descriptions are either a string or an object with `content`
+fn description(value: &Value) -> String {
+    match value.get("description") {
+        Some(Value::String(description)) => description.trim().to_string(),
+        Some(description) => description
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        None => String::new(),
+    }
+}
+
+fn disabled(value: &Value) -> bool {
+    value
+        .get("disabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+fn join(value: &Value, separator: &str) -> String {
+    match value {
+        Value::Sequence(parts) => parts.iter().map(scalar).collect::<Vec<_>>().join(separator),
+        other => scalar(other),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        // Path segments may be objects with a `value`
+        other => other
+            .get("value")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+  "info": {
+    "name": "Shop",
+    "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+  },
+  "variable": [{ "key": "baseUrl", "value": "https://api.shop.example.com/v1" }],
+  "item": [
+    {
+      "name": "Users",
+      "description": "User accounts",
+      "item": [
+        {
+          "name": "Get user",
+          "request": {
+            "method": "GET",
+            "url": {
+              "raw": "{{baseUrl}}/users/:userId?expand=orders",
+              "host": ["{{baseUrl}}"],
+              "path": ["users", ":userId"],
+              "query": [
+                { "key": "expand", "value": "orders", "description": "Related objects" },
+                { "key": "debug", "value": "1", "disabled": true }
+              ],
+              "variable": [{ "key": "userId", "description": "User identifier" }]
+            }
+          }
+        },
+        {
+          "name": "Create user",
+          "request": {
+            "method": "POST",
+            "description": "Registers a user",
+            "url": "{{baseUrl}}/users",
+            "body": { "mode": "raw", "raw": "{\"email\": \"a@b.c\", \"age\": {{age}}}" }
+          }
+        }
+      ]
+    },
+    {
+      "name": "Health",
+      "request": { "method": "GET", "url": "https://status.shop.example.com/health" }
+    }
+  ]
+}"#;
+
+    #[test]
+    fn maps_folders_requests_and_variables() {
+        let document: Value = serde_yaml::from_str(COLLECTION).unwrap();
+        assert!(detect(&document));
+
+        let catalog = import(&document).unwrap();
+        let names: Vec<&str> = catalog
+            .api_groups
+            .iter()
+            .map(|group| group.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Users", "Shop"]);
+
+        let users = &catalog.api_groups[0];
+        assert_eq!(users.base, "https://api.shop.example.com/v1");
+        assert_eq!(users.description, "User accounts");
+
+        let get = &users.endpoints[0];
+        assert_eq!(
+            (get.verb.as_str(), get.path.as_str()),
+            ("GET", "/users/{userId}")
+        );
+        assert_eq!(get.base, None);
+        let parameters: Vec<(&str, bool, &str)> = get
+            .parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.as_str(),
+                    parameter.required,
+                    parameter.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![
+                ("userId", true, "User identifier"),
+                ("expand", false, "Related objects"),
+            ]
+        );
+
+        let create = &users.endpoints[1];
+        let body: Vec<&str> = create
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        assert_eq!(body, vec!["email", "age"]);
+
+        assert_eq!(
+            catalog.api_groups[1].base,
+            "https://status.shop.example.com"
+        );
+    }
+}
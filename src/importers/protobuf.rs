@@ -0,0 +1,560 @@
+use std::error::Error;
+
+use super::humanize;
+use super::tokens::{Cursor, Punctuation};
+use crate::catalog::{ApiCatalog, ApiGroup, Endpoint, Parameter};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Punct(char),
+    /// A comment, and whether it stands on its own line rather than
+    /// trailing a statement
+    Comment(String, bool),
+}
+
+impl Punctuation for Token {
+    fn punct(&self) -> Option<char> {
+        match self {
+            Token::Punct(c) => Some(*c),
+            _ => None,
+        }
+    }
+}
+
+/// A message and its fields as parameters, keyed by its nested name
+struct Message {
+    name: String,
+    fields: Vec<Parameter>,
+}
+
+struct Rpc {
+    name: String,
+    description: String,
+    request: String,
+}
+
+struct Service {
+    name: String,
+    description: String,
+    rpcs: Vec<Rpc>,
+}
+
+/// True for Protocol Buffers files declaring a service with at least one rpc
+pub fn detect(input_content: &str) -> bool {
+    let tokens: Vec<Token> = match tokenize(input_content) {
+        Some(tokens) => tokens
+            .into_iter()
+            .filter(|token| !matches!(token, Token::Comment(..)))
+            .collect(),
+        None => return false,
+    };
+    let service = tokens.windows(3).position(|window| {
+        matches!(
+            window,
+            [Token::Word(keyword), Token::Word(_), Token::Punct('{')] if keyword == "service"
+        )
+    });
+    service.is_some_and(|start| {
+        tokens[start..].windows(3).any(|window| {
+            matches!(
+                window,
+                [Token::Word(keyword), Token::Word(_), Token::Punct('(')] if keyword == "rpc"
+            )
+        })
+    })
+}
+
+/// Maps each service to a group and each rpc to a POST endpoint on its gRPC
+/// path `/package.Service/Method`, the fields of the request message being
+/// the parameters. Fields are required when labelled `required` (proto2) or
+/// marked `(google.api.field_behavior) = REQUIRED`. Comments above a
+/// service, rpc or field, or trailing a field, give the descriptions.
+pub fn import(input_content: &str) -> Result<ApiCatalog, Box<dyn Error>> {
+    let tokens = tokenize(input_content).ok_or("Unterminated string or comment in proto file")?;
+    let mut parser = Parser {
+        tokens: Cursor::new(tokens),
+    };
+
+    let mut package = String::new();
+    let mut messages: Vec<Message> = Vec::new();
+    let mut services: Vec<Service> = Vec::new();
+
+    while parser.tokens.peek().is_some() {
+        let comment = parser.comments();
+        if let Some(Token::Word(word)) = parser.tokens.next() {
+            match word.as_str() {
+                "package" => {
+                    if let Some(Token::Word(name)) = parser.tokens.next() {
+                        package = name;
+                    }
+                    parser.skip_statement();
+                }
+                "message" => parser.message("", &mut messages),
+                "service" => services.extend(parser.service(comment)),
+                _ => parser.skip_statement(),
+            }
+        }
+    }
+
+    let groups: Vec<ApiGroup> = services
+        .into_iter()
+        .map(|service| {
+            let qualified = if package.is_empty() {
+                service.name.clone()
+            } else {
+                format!("{}.{}", package, service.name)
+            };
+            ApiGroup {
+                description: service.description,
+                base: String::new(),
+                endpoints: service
+                    .rpcs
+                    .into_iter()
+                    .map(|rpc| Endpoint {
+                        text: humanize(&rpc.name),
+                        description: rpc.description,
+                        verb: "POST".to_string(),
+                        path: format!("/{}/{}", qualified, rpc.name),
+                        parameters: find_message(&messages, &package, &rpc.request)
+                            .map(|message| message.fields.clone())
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                name: service.name,
+            }
+        })
+        .collect();
+
+    if groups.is_empty() {
+        return Err("Proto file declares no service".into());
+    }
+    Ok(ApiCatalog { api_groups: groups })
+}
+
+/// Message a request type refers to, relative to the package or fully
+/// qualified with a leading dot
+fn find_message<'a>(messages: &'a [Message], package: &str, request: &str) -> Option<&'a Message> {
+    let request = request.trim_start_matches('.');
+    let request = request
+        .strip_prefix(package)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .filter(|_| !package.is_empty())
+        .unwrap_or(request);
+    messages.iter().find(|message| message.name == request)
+}
+
+struct Parser {
+    tokens: Cursor<Token>,
+}
+
+impl Parser {
+    fn word(&mut self) -> String {
+        self.tokens
+            .take(|token| match token {
+                Token::Word(word) => Some(word.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Comments standing on their own lines before the next statement
+    fn comments(&mut self) -> String {
+        let mut lines = Vec::new();
+        while let Some(Token::Comment(text, own_line)) = self.tokens.peek() {
+            // Trailing comments of statements the import skips are dropped
+            if *own_line {
+                lines.push(text.clone());
+            }
+            self.tokens.advance();
+        }
+        lines.join(" ").trim().to_string()
+    }
+
+    /// Comment trailing the statement just read, on the same line
+    fn trailing_comment(&mut self) -> Option<String> {
+        match self.tokens.peek() {
+            Some(Token::Comment(text, false)) => {
+                let text = text.clone();
+                self.tokens.advance();
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    /// Skips to the end of a statement, past its `;` or its `{ ... }` body
+    fn skip_statement(&mut self) {
+        while let Some(token) = self.tokens.next() {
+            match token {
+                Token::Punct(';') => return,
+                Token::Punct('{') => {
+                    self.tokens.skip_block();
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads a message body, nested messages included, after `message`
+    fn message(&mut self, parent: &str, messages: &mut Vec<Message>) {
+        let name = match self.word() {
+            name if parent.is_empty() => name,
+            name => format!("{}.{}", parent, name),
+        };
+        if !self.tokens.eat('{') {
+            return;
+        }
+        let index = messages.len();
+        messages.push(Message {
+            name: name.clone(),
+            fields: Vec::new(),
+        });
+        let fields = self.fields(&name, messages, false);
+        messages[index].fields = fields;
+    }
+
+    /// Fields of a message or oneof body, up to its closing brace
+    fn fields(
+        &mut self,
+        message: &str,
+        messages: &mut Vec<Message>,
+        in_oneof: bool,
+    ) -> Vec<Parameter> {
+        let mut fields: Vec<Parameter> = Vec::new();
+        loop {
+            let comment = self.comments();
+            let word = match self.tokens.next() {
+                Some(Token::Word(word)) => word,
+                Some(Token::Punct('}')) | None => return fields,
+                Some(_) => continue,
+            };
+            match word.as_str() {
+                "message" => self.message(message, messages),
+                "oneof" => {
+                    self.word();
+                    if self.tokens.eat('{') {
+                        fields.extend(self.fields(message, messages, true));
+                    }
+                }
+                "enum" | "option" | "reserved" | "extensions" | "extend" => self.skip_statement(),
+                _ => {
+                    let label = match word.as_str() {
+                        "required" | "optional" | "repeated" => {
+                            self.word();
+                            word.as_str()
+                        }
+                        "map" => {
+                            // map<K, V> name = 1;
+                            while let Some(token) = self.tokens.next() {
+                                if token == Token::Punct('>') {
+                                    break;
+                                }
+                            }
+                            "map"
+                        }
+                        _ => "",
+                    };
+                    let name = self.word();
+                    let mut required = label == "required";
+                    while let Some(token) = self.tokens.next() {
+                        match token {
+                            Token::Punct(';') => break,
+                            Token::Word(word) if word == "REQUIRED" => required = true,
+                            _ => {}
+                        }
+                    }
+                    let description = match self.trailing_comment() {
+                        Some(trailing) if comment.is_empty() => trailing,
+                        _ => comment,
+                    };
+                    if !name.is_empty() {
+                        fields.push(Parameter {
+                            name,
+                            description,
+                            required: required && !in_oneof,
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads a service body after `service`
+    fn service(&mut self, description: String) -> Option<Service> {
+        let name = self.word();
+        if !self.tokens.eat('{') {
+            return None;
+        }
+        let mut rpcs = Vec::new();
+        loop {
+            let comment = self.comments();
+            match self.tokens.next() {
+                Some(Token::Word(word)) if word == "rpc" => {
+                    let rpc_name = self.word();
+                    self.tokens.eat('(');
+                    let mut request = self.word();
+                    if request == "stream" {
+                        request = self.word();
+                    }
+                    // returns (Response) then `;` or an options body
+                    self.skip_statement();
+                    rpcs.push(Rpc {
+                        name: rpc_name,
+                        description: comment,
+                        request,
+                    });
+                }
+                Some(Token::Word(_)) => self.skip_statement(),
+                Some(Token::Punct('}')) | None => break,
+                Some(_) => {}
+            }
+        }
+        Some(Service {
+            name,
+            description,
+            rpcs,
+        })
+    }
+}
+
+/// Words, strings, punctuation and comments of a proto file
+fn tokenize(input_content: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input_content.chars().peekable();
+    // Whether anything but whitespace came before on the current line
+    let mut line_started = false;
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '\n' => {
+                chars.next();
+                line_started = false;
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '/' => {
+                chars.next();
+                let text: String = match chars.next() {
+                    Some('/') => {
+                        let mut text = String::new();
+                        while let Some(c) = chars.next_if(|c| *c != '\n') {
+                            text.push(c);
+                        }
+                        text
+                    }
+                    Some('*') => {
+                        let mut text = String::new();
+                        loop {
+                            match chars.next()? {
+                                '*' if chars.next_if_eq(&'/').is_some() => break,
+                                c => text.push(c),
+                            }
+                        }
+                        text.lines()
+                            .map(|line| line.trim().trim_start_matches('*').trim())
+                            .filter(|line| !line.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    }
+                    _ => continue,
+                };
+                tokens.push(Token::Comment(
+                    text.trim_start_matches('/').trim().to_string(),
+                    !line_started,
+                ));
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next()? {
+                        '\\' => text.extend(chars.next()),
+                        '\n' => return None,
+                        quote if quote == c => break,
+                        other => text.push(other),
+                    }
+                }
+                tokens.push(Token::Str(text));
+                line_started = true;
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | '<' | '>' | ';' | '=' | ',' | ':' => {
+                chars.next();
+                tokens.push(Token::Punct(c));
+                line_started = true;
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || "_.-+".contains(*c)) {
+                    word.push(c);
+                }
+                if word.is_empty() {
+                    chars.next();
+                } else {
+                    tokens.push(Token::Word(word));
+                }
+                line_started = true;
+            }
+        }
+    }
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+
+package acme.users.v1;
+
+import "google/api/field_behavior.proto";
+import "google/protobuf/empty.proto";
+
+option go_package = "acme/users/v1;users";
+
+// Manages user accounts.
+service UserService {
+  option (google.api.default_host) = "users.acme.internal";
+
+  // Fetch a user by id.
+  rpc GetUser(GetUserRequest) returns (User);
+  /* Create a user
+   * and send the welcome email. */
+  rpc CreateUser(CreateUserRequest) returns (User) {
+    option (google.api.http) = { post: "/v1/users" body: "*" };
+  }
+  rpc WatchUsers(stream .acme.users.v1.ListUsersRequest) returns (stream User);
+  rpc Ping(google.protobuf.Empty) returns (google.protobuf.Empty);
+}
+
+message GetUserRequest {
+  string user_id = 1 [(google.api.field_behavior) = REQUIRED]; // Identifier of the user
+}
+
+message CreateUserRequest {
+  // Email address used to sign in
+  string email = 1 [(google.api.field_behavior) = REQUIRED];
+  map<string, string> labels = 2;
+  Profile profile = 3;
+  oneof contact {
+    string phone = 4;
+    string fax = 5;
+  }
+
+  message Profile {
+    string display_name = 1;
+    enum Kind { KIND_UNSPECIFIED = 0; }
+  }
+  reserved 6, 7;
+}
+
+message ListUsersRequest {
+  int32 page_size = 1;
+  repeated string tags = 2;
+}
+"#;
+
+    #[test]
+    fn maps_services_and_rpcs() {
+        assert!(detect(PROTO));
+        let catalog = import(PROTO).unwrap();
+        assert_eq!(catalog.api_groups.len(), 1);
+
+        let group = &catalog.api_groups[0];
+        assert_eq!(group.name, "UserService");
+        assert_eq!(group.description, "Manages user accounts.");
+
+        let endpoints: Vec<(&str, &str, &str)> = group
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                (
+                    endpoint.text.as_str(),
+                    endpoint.verb.as_str(),
+                    endpoint.path.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                ("Get user", "POST", "/acme.users.v1.UserService/GetUser"),
+                (
+                    "Create user",
+                    "POST",
+                    "/acme.users.v1.UserService/CreateUser"
+                ),
+                (
+                    "Watch users",
+                    "POST",
+                    "/acme.users.v1.UserService/WatchUsers"
+                ),
+                ("Ping", "POST", "/acme.users.v1.UserService/Ping"),
+            ]
+        );
+        assert_eq!(group.endpoints[0].description, "Fetch a user by id.");
+        assert_eq!(
+            group.endpoints[1].description,
+            "Create a user and send the welcome email."
+        );
+
+        let get_user = &group.endpoints[0].parameters[0];
+        assert_eq!(
+            (
+                get_user.name.as_str(),
+                get_user.required,
+                get_user.description.as_str()
+            ),
+            ("user_id", true, "Identifier of the user")
+        );
+
+        let parameters = |endpoint: usize| -> Vec<(String, bool)> {
+            group.endpoints[endpoint]
+                .parameters
+                .iter()
+                .map(|parameter| (parameter.name.clone(), parameter.required))
+                .collect()
+        };
+        assert_eq!(
+            parameters(1),
+            vec![
+                ("email".into(), true),
+                ("labels".into(), false),
+                ("profile".into(), false),
+                ("phone".into(), false),
+                ("fax".into(), false),
+            ]
+        );
+        assert_eq!(
+            group.endpoints[1].parameters[0].description,
+            "Email address used to sign in"
+        );
+        assert_eq!(
+            parameters(2),
+            vec![("page_size".into(), false), ("tags".into(), false)]
+        );
+        assert!(parameters(3).is_empty());
+    }
+
+    #[test]
+    fn ignores_files_without_services() {
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        assert!(!detect(&input));
+        assert!(!detect("syntax = \"proto3\";\nmessage Empty {}"));
+    }
+
+    #[test]
+    fn ignores_prose_mentioning_services() {
+        assert!(!detect(
+            "The notification service sends a message to a user over rpc or email."
+        ));
+        assert!(!detect("syntax: the rpc service { message }"));
+    }
+}
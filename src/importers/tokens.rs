@@ -0,0 +1,94 @@
+/// Tokens of the text formats parsed by hand, as far as the cursor needs
+/// to know them
+pub trait Punctuation: Clone {
+    /// The character of a punctuation token, None for any other token
+    fn punct(&self) -> Option<char>;
+}
+
+/// Reads through the tokens of a GraphQL schema or proto file
+pub struct Cursor<T> {
+    tokens: Vec<T>,
+    position: usize,
+}
+
+impl<T: Punctuation> Cursor<T> {
+    pub fn new(tokens: Vec<T>) -> Self {
+        Cursor {
+            tokens,
+            position: 0,
+        }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.tokens.get(self.position)
+    }
+
+    pub fn next(&mut self) -> Option<T> {
+        let token = self.tokens.get(self.position)?.clone();
+        self.position += 1;
+        Some(token)
+    }
+
+    /// Moves past the token just peeked at
+    pub fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    pub fn eat(&mut self, punct: char) -> bool {
+        if self.peek().and_then(Punctuation::punct) == Some(punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes the next token when `value` reads something from it
+    pub fn take<V>(&mut self, value: impl Fn(&T) -> Option<V>) -> Option<V> {
+        let value = value(self.peek()?)?;
+        self.position += 1;
+        Some(value)
+    }
+
+    /// Skips past the brace closing the current block
+    pub fn skip_block(&mut self) {
+        self.skip_until('}');
+    }
+
+    /// Skips past `close`, over any brackets nested before it
+    pub fn skip_until(&mut self, close: char) {
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            match token.punct() {
+                Some('{' | '(' | '[') => depth += 1,
+                Some(c) if c == close && depth == 0 => return,
+                Some('}' | ')' | ']') => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Punctuation for char {
+        fn punct(&self) -> Option<char> {
+            Some(*self).filter(|c| !c.is_alphanumeric())
+        }
+    }
+
+    #[test]
+    fn skips_nested_brackets() {
+        let mut cursor = Cursor::new("a{b(c)d[e{f}]}g".chars().collect());
+        assert_eq!(
+            cursor.take(|c| c.punct().is_none().then_some(*c)),
+            Some('a')
+        );
+        assert!(cursor.eat('{'));
+        cursor.skip_block();
+        assert_eq!(cursor.next(), Some('g'));
+        assert_eq!(cursor.next(), None);
+    }
+}
@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use graflog::app_log;
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+use crate::providers::{
+    cohere::CohereProvider, mock::MockProvider, ollama::OllamaProvider,
+    openai_compatible::OpenAiCompatibleProvider,
+};
+
+/// Generation options shared by every LLM backend
+#[derive(Clone, Debug)]
+pub struct CompletionOptions {
+    /// Overrides the provider's default model when set
+    pub model: Option<String>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+}
+
+impl Default for CompletionOptions {
+    fn default() -> Self {
+        CompletionOptions {
+            model: None,
+            temperature: Some(0.1),
+            max_tokens: Some(4000),
+        }
+    }
+}
+
+impl CompletionOptions {
+    /// Reads LLM_MODEL, LLM_TEMPERATURE and LLM_MAX_TOKENS, keeping defaults for unset values
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let mut options = CompletionOptions::default();
+
+        if let Ok(model) = env::var("LLM_MODEL") {
+            if !model.trim().is_empty() {
+                options.model = Some(model);
+            }
+        }
+        if let Ok(temperature) = env::var("LLM_TEMPERATURE") {
+            options.temperature = Some(
+                temperature
+                    .parse()
+                    .map_err(|e| format!("Invalid LLM_TEMPERATURE '{}': {}", temperature, e))?,
+            );
+        }
+        if let Ok(max_tokens) = env::var("LLM_MAX_TOKENS") {
+            options.max_tokens = Some(
+                max_tokens
+                    .parse()
+                    .map_err(|e| format!("Invalid LLM_MAX_TOKENS '{}': {}", max_tokens, e))?,
+            );
+        }
+
+        Ok(options)
+    }
+}
+
+/// Returned when the model stopped because it ran out of output tokens,
+/// so the partial document is never mistaken for a complete answer
+#[derive(Debug)]
+pub struct TruncationError {
+    pub provider: String,
+    pub max_tokens: Option<u32>,
+}
+
+impl fmt::Display for TruncationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max_tokens {
+            Some(max_tokens) => write!(
+                f,
+                "{} response was truncated after {} tokens (raise LLM_MAX_TOKENS)",
+                self.provider, max_tokens
+            ),
+            None => write!(f, "{} response was truncated", self.provider),
+        }
+    }
+}
+
+impl Error for TruncationError {}
+
+/// A chat model that turns a system prompt and a user prompt into text
+#[async_trait(?Send)]
+pub trait LlmProvider: Send + Sync {
+    /// Short name used in logs and error messages
+    fn name(&self) -> &str;
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        options: &CompletionOptions,
+    ) -> Result<String, Box<dyn Error>>;
+}
+
+/// Builds the provider selected by LLM_PROVIDER (defaults to "cohere")
+pub fn provider_from_env() -> Result<Box<dyn LlmProvider>, Box<dyn Error>> {
+    let provider_name = env::var("LLM_PROVIDER").unwrap_or_else(|_| "cohere".to_string());
+
+    let provider: Box<dyn LlmProvider> = match provider_name.trim().to_lowercase().as_str() {
+        "cohere" => Box::new(CohereProvider::from_env()?),
+        "ollama" => Box::new(OllamaProvider::from_env()?),
+        "openai" => Box::new(OpenAiCompatibleProvider::from_env()?),
+        "mock" => Box::new(MockProvider::from_env()?),
+        other => return Err(format!("Unknown LLM_PROVIDER: {}", other).into()),
+    };
+
+    app_log!(info, "Using LLM provider: {}", provider.name());
+    Ok(provider)
+}
@@ -1,10 +1,15 @@
 use actix_multipart::{Field, Multipart};
-use actix_web::{web, App, Error, HttpResponse, HttpServer};
+use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer};
 use anyhow::Result;
-use format_yaml_with_ollama::format_yaml_with_cohere;
+use catalog::CatalogError;
+use catalog_validator::Severity;
+use chunking::ChunkingOptions;
+use exporters::ExportFormat;
+use format_yaml_with_ollama::{LlmFormatter, RepairOptions};
 use futures_util::stream::StreamExt;
 use futures_util::TryStreamExt;
 use graflog::{app_log, init_logging};
+use llm_provider::CompletionOptions;
 use std::env;
 use std::io::Write;
 use std::path::Path;
@@ -12,10 +17,22 @@ use tempfile::NamedTempFile;
 use uuid::Uuid;
 use graflog::LogOption;
 
+mod catalog;
+mod catalog_validator;
+mod chunking;
+mod completeness;
+mod enrichment;
+mod exporters;
 mod extract_yaml;
 mod format_yaml_with_ollama;
+mod importers;
+mod llm_provider;
 mod load_prompt;
+mod mcp_server;
 mod models;
+mod providers;
+mod sentence_coverage;
+mod yaml_repair;
 mod yaml_validator;
 
 struct AppState {
@@ -23,6 +40,7 @@ struct AppState {
     reference_data_template_path: String,
     system_prompt_path: String,
     user_prompt_path: String,
+    formatter: LlmFormatter,
 }
 
 #[actix_web::main]
@@ -30,7 +48,12 @@ async fn main() -> std::io::Result<()> {
     // Load environment variables at startup
     dotenv::dotenv().ok();
 
-    if env::var("AI_UPLOADER_PORT").is_err() {
+    // Parse command line arguments - optional "server" subcommand, or "mcp"
+    // with the catalog to serve
+    let args: Vec<String> = std::env::args().collect();
+    let mcp = args.len() > 1 && args[1] == "mcp";
+
+    if !mcp && env::var("AI_UPLOADER_PORT").is_err() {
         eprintln!("Error: AI_UPLOADER_PORT environment variable is required");
         std::process::exit(1);
     }
@@ -40,15 +63,48 @@ async fn main() -> std::io::Result<()> {
     }
 
     let log_path = env::var("LOG_PATH_API0").unwrap_or_else(|_| "/var/log/api0.log".to_string());
-       init_logging!(&log_path, "api0", "ai-uploader", &[
-    LogOption::Debug,
-    LogOption::RocketOff
-]);
+    let mut log_options = vec![LogOption::Debug, LogOption::RocketOff];
+    // stdout carries the MCP messages, logs then only go to the file
+    if mcp {
+        log_options.push(LogOption::NoConsole);
+    }
+    init_logging!(&log_path, "api0", "ai-uploader", &log_options);
+
+    if mcp {
+        let catalog_path = match args.get(2) {
+            Some(path) => path,
+            None => {
+                eprintln!("Usage: {} mcp <catalog.yaml>", args[0]);
+                std::process::exit(1);
+            }
+        };
+        let catalog = match std::fs::read_to_string(catalog_path)
+            .map_err(|e| e.into())
+            .and_then(|yaml| catalog::ApiCatalog::from_yaml(&yaml))
+        {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                app_log!(error, "Failed to load catalog {}: {}", catalog_path, e);
+                eprintln!("Error: failed to load catalog {}: {}", catalog_path, e);
+                std::process::exit(1);
+            }
+        };
+        app_log!(info, "Serving {} over MCP stdio", catalog_path);
+        let server = match mcp_server::CallTimeouts::from_env()
+            .and_then(|timeouts| mcp_server::McpServer::new(catalog, timeouts))
+        {
+            Ok(server) => server,
+            Err(e) => {
+                app_log!(error, "Failed to start the MCP server: {}", e);
+                eprintln!("Error: failed to start the MCP server: {}", e);
+                std::process::exit(1);
+            }
+        };
+        return server.serve_stdio().await;
+    }
 
-    // Parse command line arguments - optional "server" subcommand
-    let args: Vec<String> = std::env::args().collect();
     if args.len() > 1 && args[1] != "server" {
-        app_log!(info, "Usage: {} [server]", args[0]);
+        app_log!(info, "Usage: {} [server | mcp <catalog.yaml>]", args[0]);
         std::process::exit(1);
     }
 
@@ -86,6 +142,9 @@ async fn main() -> std::io::Result<()> {
     let reference_data_template_path = format!("{}/template_ref_data.yaml", base_path);
     let system_prompt_path = format!("{}/prompt/system_prompt.txt", base_path);
     let user_prompt_path = format!("{}/prompt/user_prompt.txt", base_path);
+    let repair_prompt_path = format!("{}/prompt/repair_prompt.txt", base_path);
+    let sentence_prompt_path = format!("{}/prompt/sentence_prompt.txt", base_path);
+    let enrichment_prompt_path = format!("{}/prompt/enrich_prompt.txt", base_path);
 
     app_log!(info, "Using base path: {}", base_path);
     app_log!(info, "Template file: {}", template_file_path);
@@ -114,26 +173,122 @@ async fn main() -> std::io::Result<()> {
         }
     }
 
+    let provider = match llm_provider::provider_from_env() {
+        Ok(provider) => provider,
+        Err(e) => {
+            app_log!(error, "Failed to configure LLM provider: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let completion_options = match CompletionOptions::from_env() {
+        Ok(options) => options,
+        Err(e) => {
+            app_log!(error, "Invalid LLM options: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let chunking = match ChunkingOptions::from_env() {
+        Ok(chunking) => chunking,
+        Err(e) => {
+            app_log!(error, "Invalid chunking options: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let repair_attempts = match env::var("FORMAT_REPAIR_ATTEMPTS") {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(attempts) => attempts,
+            Err(e) => {
+                app_log!(error, "Invalid FORMAT_REPAIR_ATTEMPTS '{}': {}", value, e);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => 2,
+    };
+    // Without its prompt, invalid YAML is only fixed locally
+    let repair = match repair_attempts {
+        0 => None,
+        attempts => match load_prompt::load_prompt(&repair_prompt_path) {
+            Ok(prompt_template) => Some(RepairOptions {
+                attempts,
+                prompt_template,
+            }),
+            Err(e) => {
+                app_log!(
+                    warn,
+                    "Repair prompt not loaded from {}, repair disabled: {}",
+                    repair_prompt_path,
+                    e
+                );
+                None
+            }
+        },
+    };
+    // Native imports still work without it, blank fields are left blank
+    let enrichment_prompt = match load_prompt::load_prompt(&enrichment_prompt_path) {
+        Ok(prompt_template) => Some(prompt_template),
+        Err(e) => {
+            app_log!(
+                warn,
+                "Enrichment prompt not loaded from {}, enrichment disabled: {}",
+                enrichment_prompt_path,
+                e
+            );
+            None
+        }
+    };
+    let completeness_retries = match completeness::retries_from_env() {
+        Ok(retries) => retries,
+        Err(e) => {
+            app_log!(error, "Invalid completeness options: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // Sentence regeneration is opt-in, it costs one more call per upload
+    let sentence_prompt = match env::var("FORMAT_REGENERATE_SENTENCES").as_deref() {
+        Ok("true") | Ok("1") => match load_prompt::load_prompt(&sentence_prompt_path) {
+            Ok(prompt_template) => Some(prompt_template),
+            Err(e) => {
+                app_log!(error, "Failed to load sentence prompt: {}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
     let app_state = web::Data::new(AppState {
         template_path: template_file_path,
         reference_data_template_path,
         system_prompt_path,
         user_prompt_path,
+        formatter: LlmFormatter::new(provider, completion_options)
+            .with_chunking(chunking)
+            .with_completeness_retries(completeness_retries)
+            .with_repair(repair)
+            .with_sentence_regeneration(sentence_prompt)
+            .with_enrichment(enrichment_prompt),
     });
 
     // Start HTTP server with dynamic port
     HttpServer::new(move || {
         App::new()
             .app_data(app_state.clone())
-            .route("/format-yaml", web::post().to(format_yaml_handler))
-            .route("/format-reference-data", web::post().to(format_reference_data_handler))
-            .route("/health", web::get().to(health_check))
+            .configure(configure_routes)
     })
     .bind(format!("0.0.0.0:{}", port))?
     .run()
     .await
 }
 
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/format-yaml", web::post().to(format_yaml_handler))
+        .route("/generate-clients", web::post().to(generate_clients_handler))
+        .route(
+            "/format-reference-data",
+            web::post().to(format_reference_data_handler),
+        )
+        .route("/health", web::get().to(health_check));
+}
+
 async fn health_check() -> HttpResponse {
     HttpResponse::Ok().body("Service is running")
 }
@@ -166,9 +321,21 @@ async fn save_field(field: Field) -> Result<String, Error> {
 }
 
 async fn format_yaml_handler(
+    req: HttpRequest,
     multipart: Multipart,
     app_state: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
+    // Rejected before the upload is processed
+    let export = match ExportFormat::from_request(
+        query_param(&req, "format").as_deref(),
+        req.headers()
+            .get(actix_web::http::header::ACCEPT)
+            .and_then(|value| value.to_str().ok()),
+    ) {
+        Ok(export) => export,
+        Err(e) => return Ok(HttpResponse::BadRequest().body(e)),
+    };
+
     let mut input_path = None;
 
     // Process the multipart form data
@@ -190,22 +357,23 @@ async fn format_yaml_handler(
     app_log!(info, "Processing file: {}", input_file_path);
 
     // Process the uploaded file
-    match format_yaml_with_cohere(
-        // renamed function
-        &input_file_path,
-        &app_state.template_path,
-        &app_state.system_prompt_path,
-        &app_state.user_prompt_path,
-    )
-    .await
+    match app_state
+        .formatter
+        .format_catalog_file(
+            &input_file_path,
+            &app_state.template_path,
+            &app_state.system_prompt_path,
+            &app_state.user_prompt_path,
+        )
+        .await
     {
-        Ok(formatted_yaml) => {
+        Ok(catalog) => {
             // Prepare the response
             app_log!(info, "Successfully formatted YAML");
 
             // Create a temporary file for the response
             let mut temp_file = NamedTempFile::new()?;
-            temp_file.write_all(formatted_yaml.as_bytes())?;
+            temp_file.write_all(catalog.yaml.as_bytes())?;
             temp_file.flush()?;
 
             // Clean up the input file
@@ -213,17 +381,126 @@ async fn format_yaml_handler(
                 app_log!(error, "Failed to remove temporary input file: {}", e);
             }
 
-            // Return the formatted YAML
-            Ok(HttpResponse::Ok()
-                .content_type("application/yaml")
-                .append_header((
-                    "Content-Disposition",
-                    "attachment; filename=\"formatted_output.yaml\"",
-                ))
-                .body(formatted_yaml))
+            let errors = catalog
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .count();
+            let warnings = catalog.issues.len() - errors;
+
+            // Only clients opting in get the document together with the full report
+            if export.is_none() && query_param(&req, "report").as_deref() == Some("json") {
+                return Ok(HttpResponse::Ok().json(serde_json::json!({
+                    "yaml": catalog.yaml,
+                    "input_format": catalog.input_format,
+                    "reconciliation": catalog.reconciliation,
+                    "issues": catalog.issues,
+                })));
+            }
+
+            // Return the formatted YAML or the requested export, with the
+            // endpoint reconciliation and validation counts in headers
+            let (content_type, filename, body) = match export {
+                Some(format) => match format.export(&catalog.catalog) {
+                    Ok(export) => (export.content_type, export.filename, export.body),
+                    Err(e) => {
+                        app_log!(error, "Error exporting catalog: {}", e);
+                        return Ok(HttpResponse::InternalServerError()
+                            .body(format!("Error: {}", e)));
+                    }
+                },
+                None => ("application/yaml", "formatted_output.yaml", catalog.yaml),
+            };
+            let mut response = HttpResponse::Ok();
+            response.content_type(content_type).append_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            ));
+            if let Some(input_format) = catalog.input_format {
+                response.append_header(("X-Input-Format", input_format));
+            }
+            if let Some(reconciliation) = &catalog.reconciliation {
+                response
+                    .append_header(("X-Endpoints-Input", reconciliation.input_endpoints))
+                    .append_header(("X-Endpoints-Output", reconciliation.output_endpoints))
+                    .append_header(("X-Endpoints-Recovered", reconciliation.recovered))
+                    .append_header(("X-Endpoints-Missing", reconciliation.missing.len()));
+            }
+            response
+                .append_header(("X-Validation-Errors", errors))
+                .append_header(("X-Validation-Warnings", warnings));
+            Ok(response.body(body))
         }
         Err(e) => {
             app_log!(error, "Error formatting YAML: {}", e);
+            // The model answered with YAML that does not match the catalog schema
+            if e.downcast_ref::<CatalogError>().is_some() {
+                return Ok(HttpResponse::UnprocessableEntity().body(format!("Error: {}", e)));
+            }
+            Ok(HttpResponse::InternalServerError().body(format!("Error: {}", e)))
+        }
+    }
+}
+
+fn query_param(req: &HttpRequest, name: &str) -> Option<String> {
+    url::form_urlencoded::parse(req.query_string().as_bytes())
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+async fn generate_clients_handler(
+    multipart: Multipart,
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse, Error> {
+    let mut input_path = None;
+
+    // Process the multipart form data
+    let mut multipart_data = multipart;
+
+    app_log!(info, "Processing uploaded file for client generation");
+    'field_loop: while let Ok(Some(field)) = multipart_data.try_next().await {
+        if field.name() == Some("file") {
+            input_path = Some(save_field(field).await?);
+            break 'field_loop;
+        }
+    }
+
+    let input_file_path = input_path.ok_or_else(|| {
+        app_log!(error, "No file was uploaded");
+        actix_web::error::ErrorBadRequest("No file was uploaded")
+    })?;
+
+    app_log!(info, "Processing file: {}", input_file_path);
+
+    // The clients are generated from the formatted catalog
+    let formatted = app_state
+        .formatter
+        .format_catalog_file(
+            &input_file_path,
+            &app_state.template_path,
+            &app_state.system_prompt_path,
+            &app_state.user_prompt_path,
+        )
+        .await;
+
+    // Clean up the input file
+    if let Err(e) = std::fs::remove_file(&input_file_path) {
+        app_log!(error, "Failed to remove temporary input file: {}", e);
+    }
+
+    match formatted.and_then(|catalog| exporters::clients::clients_zip(&catalog.catalog)) {
+        Ok(zip) => {
+            app_log!(info, "Successfully generated clients");
+            Ok(HttpResponse::Ok()
+                .content_type("application/zip")
+                .append_header(("Content-Disposition", "attachment; filename=\"clients.zip\""))
+                .body(zip))
+        }
+        Err(e) => {
+            app_log!(error, "Error generating clients: {}", e);
+            if e.downcast_ref::<CatalogError>().is_some() {
+                return Ok(HttpResponse::UnprocessableEntity().body(format!("Error: {}", e)));
+            }
             Ok(HttpResponse::InternalServerError().body(format!("Error: {}", e)))
         }
     }
@@ -254,13 +531,15 @@ async fn format_reference_data_handler(
     app_log!(info, "Processing file: {}", input_file_path);
 
     // Process the uploaded file using the reference data template
-    match format_yaml_with_cohere(
-        &input_file_path,
-        &app_state.reference_data_template_path,
-        &app_state.system_prompt_path,
-        &app_state.user_prompt_path,
-    )
-    .await
+    match app_state
+        .formatter
+        .format_file(
+            &input_file_path,
+            &app_state.reference_data_template_path,
+            &app_state.system_prompt_path,
+            &app_state.user_prompt_path,
+        )
+        .await
     {
         Ok(formatted_yaml) => {
             // Prepare the response
@@ -295,3 +574,320 @@ async fn format_reference_data_handler(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::header, test};
+    use providers::mock::MockProvider;
+
+    const BOUNDARY: &str = "ai-uploader-test-boundary";
+
+    fn test_state(fixture: &str) -> web::Data<AppState> {
+        web::Data::new(AppState {
+            template_path: "template.yaml".to_string(),
+            reference_data_template_path: "template_ref_data.yaml".to_string(),
+            system_prompt_path: "prompt/system_prompt.txt".to_string(),
+            user_prompt_path: "prompt/user_prompt.txt".to_string(),
+            formatter: LlmFormatter::new(
+                Box::new(MockProvider::with_fixture(fixture)),
+                CompletionOptions::default(),
+            )
+            .with_enrichment(Some("{ENDPOINTS}".to_string())),
+        })
+    }
+
+    fn multipart_request(uri: &str, field_name: &str, content: &str) -> test::TestRequest {
+        let body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"input.txt\"\r\nContent-Type: text/plain\r\n\r\n{content}\r\n--{b}--\r\n",
+            b = BOUNDARY,
+            field = field_name,
+            content = content,
+        );
+        test::TestRequest::post()
+            .uri(uri)
+            .insert_header((
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            ))
+            .set_payload(body)
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_returns_replayed_document() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/output.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", &input).to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/yaml"
+        );
+        assert_eq!(resp.headers().get("X-Validation-Errors").unwrap(), "0");
+
+        let body = test::read_body(resp).await;
+        let expected = std::fs::read_to_string("samples/output.yaml").unwrap();
+        assert_eq!(body, expected.as_bytes());
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_reports_missing_endpoints_as_a_count() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/output.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        // More endpoints than the fixture formats, one named over two lines
+        let mut input = "- name: |\n    Export all\n    dossiers\n".to_string();
+        for index in 0..30 {
+            input.push_str(&format!("- name: Endpoint {}\n", index));
+        }
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", &input).to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+        let missing: usize = resp
+            .headers()
+            .get("X-Endpoints-Missing")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(missing > 0);
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml?report=json", "file", &input).to_request(),
+        )
+        .await;
+        let report: serde_json::Value = test::read_body_json(resp).await;
+        let labels = report["reconciliation"]["missing"].as_array().unwrap();
+        assert_eq!(labels.len(), missing);
+        assert!(labels.contains(&serde_json::json!("Export all\ndossiers\n")));
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_reports_validation_issues_as_json() {
+        let mut fixture = NamedTempFile::new().unwrap();
+        fixture
+            .write_all(b"api_groups:\n  - name: Users\n    endpoints:\n      - text: Get user\n        verb: FETCH\n        path: /users\n")
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state(fixture.path().to_str().unwrap()))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml?report=json", "file", "x").to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+
+        let report: serde_json::Value = test::read_body_json(resp).await;
+        assert!(report["yaml"].as_str().unwrap().contains("FETCH"));
+        assert_eq!(report["issues"][0]["severity"], "error");
+        assert_eq!(report["issues"][0]["path"], "api_groups[0].endpoints[0].verb");
+
+        // Listing JSON among the accepted types is no opt-in
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", "x")
+                .insert_header((header::ACCEPT, "application/json, text/plain, */*"))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/yaml"
+        );
+        assert_eq!(resp.headers().get("X-Validation-Errors").unwrap(), "1");
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_imports_openapi_natively() {
+        let mut fixture = NamedTempFile::new().unwrap();
+        fixture
+            .write_all(b"- id: 0\n  suggested_sentence: Get user with id 42\n  alternatives:\n    id: [user_id]\n")
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state(fixture.path().to_str().unwrap()))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let spec = "openapi: 3.0.0\nservers:\n  - url: https://api.example.com\npaths:\n  /users/{id}:\n    get:\n      tags: [Users]\n      summary: Get user\n      parameters:\n        - name: id\n          in: path\n";
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", spec).to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+        assert_eq!(resp.headers().get("X-Input-Format").unwrap(), "openapi");
+
+        let body = test::read_body(resp).await;
+        let catalog = catalog::ApiCatalog::from_yaml(&String::from_utf8_lossy(&body)).unwrap();
+        let endpoint = &catalog.api_groups[0].endpoints[0];
+        assert_eq!(catalog.api_groups[0].base, "https://api.example.com");
+        assert_eq!(endpoint.path, "/users/{id}");
+        assert_eq!(endpoint.suggested_sentence, "Get user with id 42");
+        assert_eq!(endpoint.parameters[0].alternatives, vec!["user_id"]);
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_exports_openapi() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/output.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml?format=openapi", "file", &input).to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.oai.openapi"
+        );
+        let body = test::read_body(resp).await;
+        let document: serde_yaml::Value = serde_yaml::from_slice(&body).unwrap();
+        assert_eq!(document["openapi"], "3.1.0");
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", &input)
+                .insert_header((header::ACCEPT, "application/vnd.oai.openapi+json"))
+                .to_request(),
+        )
+        .await;
+        let document: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(document["openapi"], "3.1.0");
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml?format=wsdl", "file", &input).to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn generate_clients_returns_a_zip() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/output.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let input = std::fs::read_to_string("samples/input.txt").unwrap();
+        let resp = test::call_service(
+            &app,
+            multipart_request("/generate-clients", "file", &input).to_request(),
+        )
+        .await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/zip"
+        );
+        let body = test::read_body(resp).await;
+        let archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        assert!(archive.file_names().any(|name| name == "typescript/client.ts"));
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_rejects_missing_file_field() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/output.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "other", "x").to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_reports_provider_failure() {
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state("samples/does-not-exist.yaml"))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", "x").to_request(),
+        )
+        .await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[actix_web::test]
+    async fn format_yaml_rejects_output_outside_the_schema() {
+        let mut fixture = NamedTempFile::new().unwrap();
+        fixture
+            .write_all(b"api_groups:\n  - description: no name\n    endpoints: []\n")
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(test_state(fixture.path().to_str().unwrap()))
+                .configure(configure_routes),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            multipart_request("/format-yaml", "file", "x").to_request(),
+        )
+        .await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+        let body = test::read_body(resp).await;
+        assert!(String::from_utf8_lossy(&body).contains("api_groups[0].name: missing field"));
+    }
+
+    #[actix_web::test]
+    async fn health_check_responds() {
+        let app = test::init_service(App::new().configure(configure_routes)).await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::get().uri("/health").to_request()).await;
+        assert!(resp.status().is_success());
+    }
+}
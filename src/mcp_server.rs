@@ -0,0 +1,531 @@
+use graflog::app_log;
+use reqwest::{Client, Method};
+use serde_json::{json, Map, Value};
+use std::env;
+use std::error::Error;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::catalog::ApiCatalog;
+use crate::catalog_validator::{path_parts, path_placeholders, PathPart};
+use crate::exporters::has_body;
+use crate::exporters::tools::{self, Tool};
+
+/// Revision of the Model Context Protocol answered when the client asks for
+/// one the server does not know
+const PROTOCOL_VERSION: &str = "2024-11-05";
+const SUPPORTED_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Time limits of the API calls, so a hung API cannot block the messages
+/// that follow a tool call
+#[derive(Clone, Copy, Debug)]
+pub struct CallTimeouts {
+    pub connect: Duration,
+    /// Whole call, from connecting to reading the response body
+    pub request: Duration,
+}
+
+impl Default for CallTimeouts {
+    fn default() -> Self {
+        CallTimeouts {
+            connect: Duration::from_secs(10),
+            request: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CallTimeouts {
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let defaults = CallTimeouts::default();
+        Ok(CallTimeouts {
+            connect: seconds_from_env("MCP_CONNECT_TIMEOUT_SECS", defaults.connect)?,
+            request: seconds_from_env("MCP_REQUEST_TIMEOUT_SECS", defaults.request)?,
+        })
+    }
+}
+
+fn seconds_from_env(name: &str, default: Duration) -> Result<Duration, Box<dyn Error>> {
+    match env::var(name) {
+        Ok(value) => {
+            Ok(Duration::from_secs(value.trim().parse().map_err(|e| {
+                format!("Invalid {} '{}': {}", name, value, e)
+            })?))
+        }
+        Err(_) => Ok(default),
+    }
+}
+
+/// Serves a formatted catalog over MCP, each endpoint being a tool that
+/// calls the API
+pub struct McpServer {
+    catalog: ApiCatalog,
+    client: Client,
+    timeouts: CallTimeouts,
+}
+
+impl McpServer {
+    pub fn new(catalog: ApiCatalog, timeouts: CallTimeouts) -> Result<Self, Box<dyn Error>> {
+        let client = Client::builder()
+            .connect_timeout(timeouts.connect)
+            .timeout(timeouts.request)
+            .build()?;
+        Ok(McpServer {
+            catalog,
+            client,
+            timeouts,
+        })
+    }
+
+    /// Reads one JSON-RPC message per line on stdin and writes the
+    /// responses on stdout, until stdin is closed
+    pub async fn serve_stdio(&self) -> std::io::Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_message(&line).await {
+                stdout.write_all(response.to_string().as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+        app_log!(info, "MCP client closed stdin, stopping");
+        Ok(())
+    }
+
+    /// Response to a request, None for notifications
+    async fn handle_message(&self, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => {
+                app_log!(warn, "Invalid MCP message: {}", e);
+                return Some(error_response(Value::Null, PARSE_ERROR, e.to_string()));
+            }
+        };
+        let id = message.get("id").cloned();
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        app_log!(debug, "MCP request: {}", method);
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(serde_json::to_value(tools::mcp_tools(&self.catalog))
+                .unwrap_or_else(|_| json!({ "tools": [] }))),
+            "tools/call" => self.call_tool(params).await,
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        };
+
+        // Notifications such as notifications/initialized get no answer
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params["protocolVersion"].as_str().unwrap_or_default();
+        let version = if SUPPORTED_VERSIONS.contains(&requested) {
+            requested
+        } else {
+            PROTOCOL_VERSION
+        };
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": {} },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    /// Calls the endpoint of a tool. Failed calls are reported in the tool
+    /// result so the model can see them, only unknown tools are errors.
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params["name"].as_str().unwrap_or_default();
+        let tools = tools::tools(&self.catalog);
+        let tool = tools
+            .iter()
+            .find(|tool| tool.name == name)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Unknown tool: {}", name)))?;
+        let empty = Map::new();
+        let arguments = params["arguments"].as_object().unwrap_or(&empty);
+
+        app_log!(
+            info,
+            "Calling {} {} for tool {}",
+            tool.endpoint.verb,
+            tool.endpoint.path,
+            name
+        );
+        Ok(match self.send(tool, arguments).await {
+            Ok((success, text)) => tool_result(text, !success),
+            Err(e) => {
+                app_log!(warn, "Tool {} failed: {}", name, e);
+                tool_result(format!("Error: {}", e), true)
+            }
+        })
+    }
+
+    /// Sends the HTTP request of a tool call, returning whether the status
+    /// was a success and the status line followed by the response body
+    async fn send(
+        &self,
+        tool: &Tool<'_>,
+        arguments: &Map<String, Value>,
+    ) -> Result<(bool, String), Box<dyn Error>> {
+        let request = build_request(tool, arguments)?;
+        let mut builder = self
+            .client
+            .request(request.method.clone(), &request.url)
+            .query(&request.query);
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        let response = builder.send().await.map_err(|e| self.call_error(e))?;
+        let status = response.status();
+        let body = response.text().await.map_err(|e| self.call_error(e))?;
+        Ok((status.is_success(), format!("HTTP {}\n\n{}", status, body)))
+    }
+
+    /// Says which time limit a timed out call hit
+    fn call_error(&self, error: reqwest::Error) -> Box<dyn Error> {
+        if !error.is_timeout() {
+            error.into()
+        } else if error.is_connect() {
+            format!("Could not connect within {:?}", self.timeouts.connect).into()
+        } else {
+            format!("No response within {:?}", self.timeouts.request).into()
+        }
+    }
+}
+
+/// The HTTP call a tool invocation maps to
+#[derive(Debug)]
+struct ToolRequest {
+    method: Method,
+    url: String,
+    query: Vec<(String, String)>,
+    body: Option<Map<String, Value>>,
+}
+
+/// `base` + `path` with the placeholders filled in. The other arguments go
+/// in a JSON body for POST, PUT and PATCH, in the query string otherwise.
+fn build_request(
+    tool: &Tool<'_>,
+    arguments: &Map<String, Value>,
+) -> Result<ToolRequest, Box<dyn Error>> {
+    let endpoint = tool.endpoint;
+    let missing: Vec<&str> = endpoint
+        .parameters
+        .iter()
+        .filter(|parameter| parameter.required && !arguments.contains_key(&parameter.name))
+        .map(|parameter| parameter.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing required arguments: {}", missing.join(", ")).into());
+    }
+    if tool.base.is_empty() {
+        return Err(format!("Endpoint '{}' has no base URL", endpoint.text).into());
+    }
+
+    let placeholders = path_placeholders(&endpoint.path);
+    let mut path = String::new();
+    for part in path_parts(endpoint.path.trim()) {
+        match part {
+            PathPart::Literal(text) => path.push_str(text),
+            PathPart::Placeholder(name) => {
+                let value = arguments
+                    .get(name)
+                    .map(argument_text)
+                    .ok_or_else(|| format!("Missing path argument: {}", name))?;
+                let encoded: String = url::form_urlencoded::byte_serialize(value.as_bytes())
+                    .collect::<String>()
+                    .replace('+', "%20");
+                path.push_str(&encoded);
+            }
+        }
+    }
+    let url = format!(
+        "{}/{}",
+        tool.base.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+
+    let method = Method::from_bytes(endpoint.verb.trim().to_uppercase().as_bytes())?;
+    let in_body = has_body(method.as_str());
+    let mut query = Vec::new();
+    let mut body = Map::new();
+    for (name, value) in arguments {
+        if placeholders.contains(&name.as_str()) {
+            continue;
+        }
+        if in_body {
+            body.insert(name.clone(), value.clone());
+        } else {
+            query.push((name.clone(), argument_text(value)));
+        }
+    }
+
+    Ok(ToolRequest {
+        method,
+        url,
+        query,
+        body: in_body.then_some(body),
+    })
+}
+
+fn argument_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn catalog(base: &str) -> ApiCatalog {
+        ApiCatalog::from_yaml(&format!(
+            r#"
+api_groups:
+  - name: Users
+    base: {}
+    endpoints:
+      - text: Get user
+        description: Fetch one user
+        verb: GET
+        path: /users/{{id}}
+        parameters:
+          - name: id
+            required: true
+          - name: expand
+            required: false
+      - text: Create user
+        verb: POST
+        path: /users
+        parameters:
+          - name: email
+            required: true
+"#,
+            base
+        ))
+        .unwrap()
+    }
+
+    /// Answers one HTTP request with its request line and body
+    async fn echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            loop {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text[..end]
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + length || read == 0 {
+                        break;
+                    }
+                }
+            }
+            let text = String::from_utf8_lossy(&request).to_string();
+            let (head, body) = text.split_once("\r\n\r\n").unwrap();
+            let echo = format!("{}\n{}", head.lines().next().unwrap(), body);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                echo.len(),
+                echo
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}", address)
+    }
+
+    async fn call(server: &McpServer, message: Value) -> Value {
+        server.handle_message(&message.to_string()).await.unwrap()
+    }
+
+    #[actix_web::test]
+    async fn initializes_and_lists_tools() {
+        let server =
+            McpServer::new(catalog("https://api.example.com"), CallTimeouts::default()).unwrap();
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"protocolVersion": "2025-03-26"}}),
+        )
+        .await;
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(server
+            .handle_message(&notification.to_string())
+            .await
+            .is_none());
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        )
+        .await;
+        let tools = response["result"]["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0]["name"], "get_user");
+        assert_eq!(tools[0]["inputSchema"]["required"], json!(["id"]));
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+        let response = server.handle_message("{not json").await.unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+    }
+
+    #[actix_web::test]
+    async fn calls_the_endpoint_of_a_tool() {
+        let server =
+            McpServer::new(catalog(&echo_server().await), CallTimeouts::default()).unwrap();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                   "params": {"name": "get_user", "arguments": {"id": "4 2", "expand": "orders"}}}),
+        )
+        .await;
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("HTTP 200 OK"));
+        assert!(text.contains("GET /users/4%202?expand=orders HTTP/1.1"));
+
+        let server =
+            McpServer::new(catalog(&echo_server().await), CallTimeouts::default()).unwrap();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                   "params": {"name": "create_user", "arguments": {"email": "ann@example.com"}}}),
+        )
+        .await;
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("POST /users HTTP/1.1"));
+        assert!(text.contains(r#"{"email":"ann@example.com"}"#));
+    }
+
+    #[actix_web::test]
+    async fn reports_invalid_calls() {
+        let server =
+            McpServer::new(catalog("https://api.example.com"), CallTimeouts::default()).unwrap();
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                   "params": {"name": "get_user", "arguments": {}}}),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Error: Missing required arguments: id"
+        );
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "delete_user"}}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn fills_placeholders_written_with_spaces() {
+        let catalog = ApiCatalog::from_yaml(
+            r#"
+api_groups:
+  - name: Users
+    base: https://api.example.com
+    endpoints:
+      - text: Get user
+        verb: GET
+        path: /users/{ id }
+        parameters:
+          - name: id
+            required: true
+"#,
+        )
+        .unwrap();
+        let tools = tools::tools(&catalog);
+        let arguments = json!({ "id": "4 2" }).as_object().unwrap().clone();
+        let request = build_request(&tools[0], &arguments).unwrap();
+        assert_eq!(request.url, "https://api.example.com/users/4%202");
+        assert!(request.query.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn reports_calls_that_time_out() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+        let timeouts = CallTimeouts {
+            request: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let server = McpServer::new(catalog(&base), timeouts).unwrap();
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                   "params": {"name": "get_user", "arguments": {"id": "42"}}}),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Error: No response within 200ms"
+        );
+    }
+}
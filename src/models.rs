@@ -3,19 +3,149 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize)]
 pub struct CohereRequest {
     pub model: String,
+    pub messages: Vec<CohereMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "role", rename_all = "lowercase")]
+pub enum CohereMessage {
+    System { content: String },
+    User { content: String },
+    Assistant { content: String },
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CohereResponse {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub finish_reason: CohereFinishReason,
+    pub message: CohereResponseMessage,
+    #[serde(default)]
+    pub usage: Option<CohereUsage>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CohereFinishReason {
+    Complete,
+    StopSequence,
+    MaxTokens,
+    ToolCall,
+    Error,
+    Timeout,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CohereResponseMessage {
+    #[serde(default)]
+    pub content: Vec<CohereContent>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CohereContent {
+    Text {
+        text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct CohereUsage {
+    #[serde(default)]
+    pub billed_units: Option<CohereTokenCounts>,
+    #[serde(default)]
+    pub tokens: Option<CohereTokenCounts>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct CohereTokenCounts {
+    #[serde(default)]
+    pub input_tokens: f64,
+    #[serde(default)]
+    pub output_tokens: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CohereErrorBody {
+    #[serde(default)]
+    pub id: Option<String>,
     pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<OllamaMessage>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    pub options: OllamaOptions,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OllamaMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+pub struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OllamaChatResponse {
+    pub message: OllamaResponseMessage,
+    #[serde(default)]
+    pub done_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OllamaResponseMessage {
+    pub content: String,
+}
+
+#[derive(Serialize)]
+pub struct OpenAiChatRequest {
+    pub model: String,
+    pub messages: Vec<OpenAiMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f64>,
-    pub chat_history: Vec<ChatMessage>,
 }
 
 #[derive(Serialize, Deserialize)]
-pub struct ChatMessage {
+pub struct OpenAiMessage {
     pub role: String,
-    pub message: String,
+    pub content: String,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct CohereResponse {
-    pub text: String,
+pub struct OpenAiChatResponse {
+    pub choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OpenAiChoice {
+    pub message: OpenAiResponseMessage,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OpenAiResponseMessage {
+    #[serde(default)]
+    pub content: Option<String>,
 }
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use graflog::app_log;
+use reqwest::Client;
+use std::env;
+use std::error::Error;
+
+use crate::{
+    llm_provider::{CompletionOptions, LlmProvider, TruncationError},
+    models::{
+        CohereContent, CohereErrorBody, CohereFinishReason, CohereMessage, CohereRequest,
+        CohereResponse,
+    },
+};
+
+const COHERE_CHAT_URL: &str = "https://api.cohere.com/v2/chat";
+const DEFAULT_COHERE_MODEL: &str = "command-r7b-12-2024"; // or "command-r-08-2024"
+
+pub struct CohereProvider {
+    client: Client,
+    api_key: String,
+}
+
+impl CohereProvider {
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let api_key = env::var("COHERE_API_KEY")
+            .map_err(|_| "COHERE_API_KEY not found in environment variables")?;
+
+        Ok(CohereProvider {
+            client: Client::new(),
+            api_key,
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl LlmProvider for CohereProvider {
+    fn name(&self) -> &str {
+        "cohere"
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        options: &CompletionOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let request = CohereRequest {
+            model: options
+                .model
+                .clone()
+                .unwrap_or_else(|| DEFAULT_COHERE_MODEL.to_string()),
+            messages: vec![
+                CohereMessage::System {
+                    content: system_prompt.to_string(),
+                },
+                CohereMessage::User {
+                    content: user_prompt.to_string(),
+                },
+            ],
+            max_tokens: options.max_tokens,
+            temperature: options.temperature,
+        };
+
+        app_log!(info, "Calling Cohere API");
+        let resp = self
+            .client
+            .post(COHERE_CHAT_URL)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_text = resp.text().await?;
+            let message = match serde_json::from_str::<CohereErrorBody>(&error_text) {
+                Ok(body) => {
+                    app_log!(
+                        error,
+                        "Failed to call Cohere ({}, request id {:?}): {}",
+                        status,
+                        body.id,
+                        body.message
+                    );
+                    body.message
+                }
+                Err(_) => {
+                    app_log!(error, "Failed to call Cohere ({}): {}", status, error_text);
+                    error_text
+                }
+            };
+            return Err(format!("Cohere API error ({}): {}", status, message).into());
+        }
+
+        let cohere_response: CohereResponse = resp.json().await?;
+        app_log!(
+            info,
+            "Received response from Cohere (id {:?}, finish reason {:?})",
+            cohere_response.id,
+            cohere_response.finish_reason
+        );
+
+        if let Some(tokens) = cohere_response
+            .usage
+            .as_ref()
+            .and_then(|usage| usage.tokens.as_ref().or(usage.billed_units.as_ref()))
+        {
+            app_log!(
+                info,
+                "Cohere token usage: {} input, {} output",
+                tokens.input_tokens,
+                tokens.output_tokens
+            );
+        }
+
+        response_text(cohere_response, options)
+    }
+}
+
+/// Turns a completed v2 response into its text, rejecting truncated or aborted answers
+fn response_text(
+    response: CohereResponse,
+    options: &CompletionOptions,
+) -> Result<String, Box<dyn Error>> {
+    match response.finish_reason {
+        CohereFinishReason::Complete | CohereFinishReason::StopSequence => {}
+        CohereFinishReason::MaxTokens => {
+            app_log!(warn, "Cohere stopped at the max_tokens limit");
+            return Err(Box::new(TruncationError {
+                provider: "cohere".to_string(),
+                max_tokens: options.max_tokens,
+            }));
+        }
+        other => {
+            return Err(format!("Cohere did not complete the answer: {:?}", other).into());
+        }
+    }
+
+    let text: String = response
+        .message
+        .content
+        .into_iter()
+        .filter_map(|content| match content {
+            CohereContent::Text { text } => Some(text),
+            CohereContent::Other => None,
+        })
+        .collect();
+
+    if text.trim().is_empty() {
+        return Err("Cohere response contained no text".into());
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(body: &str) -> CohereResponse {
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[test]
+    fn returns_text_of_completed_response() {
+        let response = parse(
+            r#"{"id":"abc","finish_reason":"COMPLETE","message":{"role":"assistant","content":[{"type":"text","text":"api_groups: []"}]},"usage":{"tokens":{"input_tokens":10,"output_tokens":4}}}"#,
+        );
+
+        let text = response_text(response, &CompletionOptions::default()).unwrap();
+        assert_eq!(text, "api_groups: []");
+    }
+
+    #[test]
+    fn max_tokens_is_a_truncation_error() {
+        let response = parse(
+            r#"{"id":"abc","finish_reason":"MAX_TOKENS","message":{"role":"assistant","content":[{"type":"text","text":"api_groups:\n  - name: "}]}}"#,
+        );
+
+        let err = response_text(response, &CompletionOptions::default()).unwrap_err();
+        assert!(err.downcast_ref::<TruncationError>().is_some());
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use graflog::app_log;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::llm_provider::{CompletionOptions, LlmProvider};
+
+/// Offline provider replaying canned responses from disk.
+///
+/// A response is looked up as `<fixture_dir>/<hash>.yaml`, where `<hash>` is the
+/// FNV-1a hash of the user prompt, and falls back to `default_fixture`.
+pub struct MockProvider {
+    fixture_dir: Option<PathBuf>,
+    default_fixture: Option<PathBuf>,
+}
+
+impl MockProvider {
+    /// Reads MOCK_LLM_FIXTURE_DIR and MOCK_LLM_FIXTURE, at least one must be set
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let fixture_dir = env::var("MOCK_LLM_FIXTURE_DIR").ok().map(PathBuf::from);
+        let default_fixture = env::var("MOCK_LLM_FIXTURE").ok().map(PathBuf::from);
+
+        if fixture_dir.is_none() && default_fixture.is_none() {
+            return Err(
+                "MOCK_LLM_FIXTURE or MOCK_LLM_FIXTURE_DIR is required for the mock provider".into(),
+            );
+        }
+
+        Ok(MockProvider {
+            fixture_dir,
+            default_fixture,
+        })
+    }
+
+    /// Replays the same file for every prompt
+    #[cfg(test)]
+    pub fn with_fixture(path: impl Into<PathBuf>) -> Self {
+        MockProvider {
+            fixture_dir: None,
+            default_fixture: Some(path.into()),
+        }
+    }
+
+    /// Replays `<dir>/<hash>.yaml` for each prompt, with no fallback
+    #[cfg(test)]
+    pub fn with_fixture_dir(dir: impl Into<PathBuf>) -> Self {
+        MockProvider {
+            fixture_dir: Some(dir.into()),
+            default_fixture: None,
+        }
+    }
+
+    fn fixture_for(&self, prompt_hash: &str) -> Option<PathBuf> {
+        if let Some(dir) = &self.fixture_dir {
+            let keyed = dir.join(format!("{}.yaml", prompt_hash));
+            if keyed.exists() {
+                return Some(keyed);
+            }
+        }
+        self.default_fixture
+            .as_ref()
+            .filter(|path| Path::new(path).exists())
+            .cloned()
+    }
+}
+
+/// Stable 64-bit FNV-1a hash, unlike `DefaultHasher` it does not change between Rust releases
+pub fn prompt_hash(prompt: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in prompt.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[async_trait(?Send)]
+impl LlmProvider for MockProvider {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn complete(
+        &self,
+        _system_prompt: &str,
+        user_prompt: &str,
+        _options: &CompletionOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let hash = prompt_hash(user_prompt);
+        let fixture = self
+            .fixture_for(&hash)
+            .ok_or_else(|| format!("No mock fixture found for prompt hash {}", hash))?;
+
+        app_log!(info, "Replaying mock fixture {}", fixture.display());
+        Ok(fs::read_to_string(fixture)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_web::test]
+    async fn replays_fixture_keyed_by_prompt_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(format!("{}.yaml", prompt_hash("keyed"))),
+            "keyed: true",
+        )
+        .unwrap();
+        let provider = MockProvider {
+            fixture_dir: Some(dir.path().to_path_buf()),
+            default_fixture: Some(PathBuf::from("samples/output.yaml")),
+        };
+        let options = CompletionOptions::default();
+
+        let keyed = provider.complete("", "keyed", &options).await.unwrap();
+        assert_eq!(keyed, "keyed: true");
+
+        let fallback = provider.complete("", "other", &options).await.unwrap();
+        assert!(fallback.starts_with("api_groups:"));
+    }
+
+    #[actix_web::test]
+    async fn fails_without_matching_fixture() {
+        let provider = MockProvider {
+            fixture_dir: None,
+            default_fixture: Some(PathBuf::from("samples/missing.yaml")),
+        };
+
+        let result = provider
+            .complete("", "prompt", &CompletionOptions::default())
+            .await;
+        assert!(result.is_err());
+    }
+}
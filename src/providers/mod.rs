@@ -0,0 +1,4 @@
+pub mod cohere;
+pub mod mock;
+pub mod ollama;
+pub mod openai_compatible;
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use graflog::app_log;
+use reqwest::Client;
+use std::env;
+use std::error::Error;
+
+use crate::{
+    llm_provider::{CompletionOptions, LlmProvider, TruncationError},
+    models::{OllamaChatRequest, OllamaChatResponse, OllamaMessage, OllamaOptions},
+};
+
+const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+const DEFAULT_OLLAMA_MODEL: &str = "llama3.1";
+
+pub struct OllamaProvider {
+    client: Client,
+    host: String,
+    model: String,
+    format: Option<String>,
+}
+
+impl OllamaProvider {
+    /// Reads OLLAMA_HOST, OLLAMA_MODEL and the optional OLLAMA_FORMAT (e.g. "json")
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let host = env::var("OLLAMA_HOST").unwrap_or_else(|_| DEFAULT_OLLAMA_HOST.to_string());
+        let model = env::var("OLLAMA_MODEL").unwrap_or_else(|_| DEFAULT_OLLAMA_MODEL.to_string());
+        let format = env::var("OLLAMA_FORMAT")
+            .ok()
+            .filter(|format| !format.trim().is_empty());
+
+        Ok(OllamaProvider {
+            client: Client::new(),
+            host: host.trim_end_matches('/').to_string(),
+            model,
+            format,
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        options: &CompletionOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let request = OllamaChatRequest {
+            model: options.model.clone().unwrap_or_else(|| self.model.clone()),
+            messages: vec![
+                OllamaMessage {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                OllamaMessage {
+                    role: "user".to_string(),
+                    content: user_prompt.to_string(),
+                },
+            ],
+            stream: false,
+            format: self.format.clone(),
+            options: OllamaOptions {
+                temperature: options.temperature,
+                num_predict: options.max_tokens,
+            },
+        };
+
+        app_log!(info, "Calling Ollama at {}", self.host);
+        let resp = self
+            .client
+            .post(format!("{}/api/chat", self.host))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let error_text = resp.text().await?;
+            app_log!(error, "Failed to call Ollama: {}", error_text);
+            return Err(format!("Ollama API error: {}", error_text).into());
+        }
+
+        let ollama_response: OllamaChatResponse = resp.json().await?;
+        app_log!(info, "Received response from Ollama");
+
+        if ollama_response.done_reason.as_deref() == Some("length") {
+            return Err(Box::new(TruncationError {
+                provider: "ollama".to_string(),
+                max_tokens: options.max_tokens,
+            }));
+        }
+
+        // JSON mode answers with a JSON document, convert it so the YAML pipeline sees YAML
+        if self.format.as_deref() == Some("json") {
+            let value: serde_json::Value =
+                serde_json::from_str(&ollama_response.message.content)
+                    .map_err(|e| format!("Ollama returned invalid JSON: {}", e))?;
+            return Ok(serde_yaml::to_string(&value)?);
+        }
+
+        Ok(ollama_response.message.content)
+    }
+}
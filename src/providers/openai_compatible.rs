@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use graflog::app_log;
+use reqwest::Client;
+use std::env;
+use std::error::Error;
+
+use crate::{
+    llm_provider::{CompletionOptions, LlmProvider, TruncationError},
+    models::{OpenAiChatRequest, OpenAiChatResponse, OpenAiMessage},
+};
+
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+
+/// Any server speaking the OpenAI `/chat/completions` format (OpenAI, Azure, vLLM, llama.cpp, LM Studio)
+pub struct OpenAiCompatibleProvider {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    /// Set for Azure OpenAI, which takes the key in `api-key` and the
+    /// version in the `api-version` query parameter
+    api_version: Option<String>,
+    model: String,
+}
+
+impl OpenAiCompatibleProvider {
+    /// Reads OPENAI_BASE_URL, OPENAI_MODEL, the optional OPENAI_API_KEY and,
+    /// for Azure OpenAI, OPENAI_API_VERSION
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let base_url =
+            env::var("OPENAI_BASE_URL").unwrap_or_else(|_| DEFAULT_OPENAI_BASE_URL.to_string());
+        let model = env::var("OPENAI_MODEL").unwrap_or_else(|_| DEFAULT_OPENAI_MODEL.to_string());
+        // Local servers usually run without authentication
+        let api_key = env::var("OPENAI_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+        let api_version = env::var("OPENAI_API_VERSION")
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty());
+
+        Ok(OpenAiCompatibleProvider {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            api_version,
+            model,
+        })
+    }
+
+    fn chat_request(&self, request: &OpenAiChatRequest) -> reqwest::RequestBuilder {
+        let mut builder = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Content-Type", "application/json")
+            .json(request);
+        if let Some(api_version) = &self.api_version {
+            builder = builder.query(&[("api-version", api_version)]);
+        }
+        if let Some(api_key) = &self.api_key {
+            builder = match self.api_version {
+                Some(_) => builder.header("api-key", api_key),
+                None => builder.header("Authorization", format!("Bearer {}", api_key)),
+            };
+        }
+        builder
+    }
+}
+
+#[async_trait(?Send)]
+impl LlmProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        options: &CompletionOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let request = OpenAiChatRequest {
+            model: options.model.clone().unwrap_or_else(|| self.model.clone()),
+            messages: vec![
+                OpenAiMessage {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                OpenAiMessage {
+                    role: "user".to_string(),
+                    content: user_prompt.to_string(),
+                },
+            ],
+            max_tokens: options.max_tokens,
+            temperature: options.temperature,
+        };
+
+        app_log!(info, "Calling chat completions at {}", self.base_url);
+        let resp = self.chat_request(&request).send().await?;
+
+        if !resp.status().is_success() {
+            let error_text = resp.text().await?;
+            app_log!(error, "Failed to call chat completions: {}", error_text);
+            return Err(format!("Chat completions API error: {}", error_text).into());
+        }
+
+        let completion: OpenAiChatResponse = resp.json().await?;
+        app_log!(info, "Received response from chat completions");
+
+        let choice = completion
+            .choices
+            .into_iter()
+            .next()
+            .ok_or("Chat completions response contained no choices")?;
+
+        if choice.finish_reason.as_deref() == Some("length") {
+            return Err(Box::new(TruncationError {
+                provider: "openai".to_string(),
+                max_tokens: options.max_tokens,
+            }));
+        }
+
+        choice
+            .message
+            .content
+            .ok_or_else(|| "Chat completions response contained no content".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(api_version: Option<&str>) -> OpenAiCompatibleProvider {
+        OpenAiCompatibleProvider {
+            client: Client::new(),
+            base_url: "https://example.openai.azure.com/openai/deployments/gpt-4o".to_string(),
+            api_key: Some("secret".to_string()),
+            api_version: api_version.map(str::to_string),
+            model: DEFAULT_OPENAI_MODEL.to_string(),
+        }
+    }
+
+    fn request() -> OpenAiChatRequest {
+        OpenAiChatRequest {
+            model: DEFAULT_OPENAI_MODEL.to_string(),
+            messages: Vec::new(),
+            max_tokens: None,
+            temperature: None,
+        }
+    }
+
+    #[test]
+    fn sends_a_bearer_token_by_default() {
+        let built = provider(None).chat_request(&request()).build().unwrap();
+        assert_eq!(built.headers()["Authorization"], "Bearer secret");
+        assert_eq!(built.url().query(), None);
+    }
+
+    #[test]
+    fn sends_azure_key_and_version_when_a_version_is_set() {
+        let built = provider(Some("2024-06-01"))
+            .chat_request(&request())
+            .build()
+            .unwrap();
+        assert_eq!(built.headers()["api-key"], "secret");
+        assert!(built.headers().get("Authorization").is_none());
+        assert_eq!(
+            built.url().as_str(),
+            "https://example.openai.azure.com/openai/deployments/gpt-4o/chat/completions?api-version=2024-06-01"
+        );
+    }
+}
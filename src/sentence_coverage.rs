@@ -0,0 +1,395 @@
+use serde_yaml::{Mapping, Value};
+use std::error::Error;
+
+use crate::catalog::{ApiCatalog, Endpoint};
+
+/// Words that may sit between a parameter and its value: "id is 42", "id: 42"
+const CONNECTORS: &[&str] = &["is", "of", "to", "as", "equals"];
+
+/// Words that follow a parameter name without being its value
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "for", "with", "in", "on", "at", "by", "from",
+];
+
+/// Last words of a parameter name a sentence may leave out, "order 42"
+/// giving `orderId`. They are never values themselves.
+const IDENTIFIER_WORDS: &[&str] = &["id", "ids", "uuid", "key", "number"];
+
+const QUOTES: &[(char, char)] = &[('"', '"'), ('\'', '\''), ('“', '”'), ('‘', '’')];
+const TRAILING_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?', ')'];
+
+/// An endpoint whose `suggested_sentence` fails the coverage check
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlaggedSentence {
+    pub group: usize,
+    pub endpoint: usize,
+    pub problems: Vec<String>,
+}
+
+/// Reasons the suggested sentence of an endpoint cannot be used as is.
+///
+/// A required parameter counts as covered when the sentence gives it an
+/// example value after its name or one of its alternatives, so "for user id
+/// 123" covers `id` and "order 42 of user 7" covers `orderId` and `userId`.
+pub fn sentence_problems(endpoint: &Endpoint) -> Vec<String> {
+    let sentence = endpoint.suggested_sentence.trim();
+    if sentence.is_empty() {
+        return vec!["missing suggested sentence".to_string()];
+    }
+
+    let mut problems = Vec::new();
+    let placeholders = placeholders(sentence);
+    if !placeholders.is_empty() {
+        problems.push(format!(
+            "contains placeholders {} instead of example values",
+            placeholders.join(", ")
+        ));
+    }
+
+    let uncovered: Vec<&str> = endpoint
+        .parameters
+        .iter()
+        .filter(|parameter| parameter.required)
+        .filter(|parameter| {
+            let names: Vec<&str> = std::iter::once(&parameter.name)
+                .chain(&parameter.alternatives)
+                .map(String::as_str)
+                .collect();
+            sentence_value(sentence, &names).is_none()
+        })
+        .map(|parameter| parameter.name.as_str())
+        .collect();
+    if !uncovered.is_empty() {
+        problems.push(format!(
+            "gives no example value for required parameters {}",
+            uncovered.join(", ")
+        ));
+    }
+
+    problems
+}
+
+/// Every endpoint of the catalog with a sentence to regenerate
+pub fn flagged_sentences(catalog: &ApiCatalog) -> Vec<FlaggedSentence> {
+    let mut flagged = Vec::new();
+    for (group_index, group) in catalog.api_groups.iter().enumerate() {
+        for (endpoint_index, endpoint) in group.endpoints.iter().enumerate() {
+            let problems = sentence_problems(endpoint);
+            if !problems.is_empty() {
+                flagged.push(FlaggedSentence {
+                    group: group_index,
+                    endpoint: endpoint_index,
+                    problems,
+                });
+            }
+        }
+    }
+    flagged
+}
+
+/// Input for the regeneration prompt: the flagged endpoints, numbered by
+/// their position in `flagged`, with what is wrong with their sentence
+pub fn regeneration_input(
+    catalog: &ApiCatalog,
+    flagged: &[FlaggedSentence],
+) -> Result<String, Box<dyn Error>> {
+    let mut items = Vec::new();
+    for (id, sentence) in flagged.iter().enumerate() {
+        let endpoint = &catalog.api_groups[sentence.group].endpoints[sentence.endpoint];
+        let mut item = Mapping::new();
+        item.insert(Value::from("id"), Value::from(id));
+        item.insert(Value::from("text"), Value::from(endpoint.text.as_str()));
+        item.insert(
+            Value::from("description"),
+            Value::from(endpoint.description.as_str()),
+        );
+        item.insert(
+            Value::from("path"),
+            Value::from(format!("{} {}", endpoint.verb, endpoint.path)),
+        );
+        item.insert(
+            Value::from("parameters"),
+            serde_yaml::to_value(&endpoint.parameters)?,
+        );
+        item.insert(
+            Value::from("suggested_sentence"),
+            Value::from(endpoint.suggested_sentence.as_str()),
+        );
+        item.insert(
+            Value::from("problems"),
+            serde_yaml::to_value(&sentence.problems)?,
+        );
+        items.push(Value::Mapping(item));
+    }
+    Ok(serde_yaml::to_string(&Value::Sequence(items))?)
+}
+
+/// Writes the regenerated sentences that pass the check into the document.
+/// Returns the updated document and how many sentences were replaced.
+pub fn apply_sentences(
+    yaml_content: &str,
+    catalog: &ApiCatalog,
+    flagged: &[FlaggedSentence],
+    answer_yaml: &str,
+) -> Result<(String, usize), Box<dyn Error>> {
+    let answer: Value = serde_yaml::from_str(answer_yaml)?;
+    let answers = answer
+        .as_sequence()
+        .ok_or("Regenerated sentences are not a list")?;
+    let mut document: Value = serde_yaml::from_str(yaml_content)?;
+
+    let mut replaced = 0;
+    for item in answers {
+        let (id, sentence) = match (
+            item.get("id").and_then(Value::as_u64),
+            item.get("suggested_sentence").and_then(Value::as_str),
+        ) {
+            (Some(id), Some(sentence)) => (id as usize, sentence.trim()),
+            _ => continue,
+        };
+        let target = match flagged.get(id) {
+            Some(target) => target,
+            None => continue,
+        };
+
+        // Keep the old sentence unless the new one fixes it
+        let mut endpoint = catalog.api_groups[target.group].endpoints[target.endpoint].clone();
+        endpoint.suggested_sentence = sentence.to_string();
+        if !sentence_problems(&endpoint).is_empty() {
+            continue;
+        }
+
+        if let Some(Value::Mapping(mapping)) = document
+            .get_mut("api_groups")
+            .and_then(|groups| groups.get_mut(target.group))
+            .and_then(|group| group.get_mut("endpoints"))
+            .and_then(|endpoints| endpoints.get_mut(target.endpoint))
+        {
+            mapping.insert(Value::from("suggested_sentence"), Value::from(sentence));
+            replaced += 1;
+        }
+    }
+
+    if replaced == 0 {
+        return Ok((yaml_content.to_string(), 0));
+    }
+    Ok((serde_yaml::to_string(&document)?, replaced))
+}
+
+/// `{id}`, `{{id}}` and `<id>` style placeholders of a sentence
+fn placeholders(sentence: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for (open, close) in [('{', '}'), ('<', '>')] {
+        let mut rest = sentence;
+        while let Some(start) = rest.find(open) {
+            match rest[start..].find(close) {
+                Some(end) => {
+                    let inner = rest[start + 1..start + end].trim_matches(open).trim();
+                    if !inner.is_empty() {
+                        found.push(format!("{}{}{}", open, inner, close));
+                    }
+                    rest = &rest[start + end + 1..];
+                }
+                None => break,
+            }
+        }
+    }
+    found
+}
+
+/// Lowercase words of a text, splitting snake_case, kebab-case and camelCase
+pub fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            previous_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// The value the sentence gives a parameter, the word after its name or
+/// one of its alternatives: "Get user with id 42" gives 42 for `id`
+pub fn sentence_value(sentence: &str, names: &[&str]) -> Option<String> {
+    let tokens: Vec<&str> = sentence.split_whitespace().collect();
+    let sentence_words: Vec<(String, usize)> = tokens
+        .iter()
+        .enumerate()
+        // A placeholder names the parameter without giving it a value
+        .filter(|(_, token)| !token.contains(['{', '<']))
+        .flat_map(|(index, token)| words(token).into_iter().map(move |word| (word, index)))
+        .collect();
+
+    let mut spellings: Vec<Vec<String>> = names.iter().map(|name| words(name)).collect();
+    // Then without a trailing `id`, once the full names gave nothing
+    spellings.extend(
+        spellings
+            .iter()
+            .filter(|name_words| {
+                name_words.len() > 1
+                    && IDENTIFIER_WORDS.contains(&name_words[name_words.len() - 1].as_str())
+            })
+            .map(|name_words| name_words[..name_words.len() - 1].to_vec())
+            .collect::<Vec<_>>(),
+    );
+
+    for name_words in spellings.iter().filter(|name_words| !name_words.is_empty()) {
+        // The name spelled as one word ("userid") or word by word ("user id")
+        let joined = name_words.concat();
+        for start in 0..sentence_words.len() {
+            let last = if sentence_words[start].0 == joined {
+                start
+            } else if sentence_words[start..].len() >= name_words.len()
+                && name_words
+                    .iter()
+                    .zip(&sentence_words[start..])
+                    .all(|(word, (sentence_word, _))| word == sentence_word)
+            {
+                start + name_words.len() - 1
+            } else {
+                continue;
+            };
+            if let Some(value) = value_after(&tokens[sentence_words[last].1 + 1..]) {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// The first word of `tokens` after connectors, or the quoted text there
+fn value_after(tokens: &[&str]) -> Option<String> {
+    let mut tokens = tokens.iter().skip_while(|token| {
+        let token_words = words(token);
+        token_words.is_empty()
+            || (token_words.len() == 1 && CONNECTORS.contains(&token_words[0].as_str()))
+    });
+    let first = *tokens.next()?;
+
+    let value = match QUOTES.iter().find(|(open, _)| first.starts_with(*open)) {
+        Some((open, close)) => {
+            let mut text = first[open.len_utf8()..].to_string();
+            while !text
+                .trim_end_matches(TRAILING_PUNCTUATION)
+                .ends_with(*close)
+            {
+                match tokens.next() {
+                    Some(token) => {
+                        text.push(' ');
+                        text.push_str(token);
+                    }
+                    None => break,
+                }
+            }
+            text.trim_end_matches(TRAILING_PUNCTUATION)
+                .trim_end_matches(*close)
+                .to_string()
+        }
+        None => first
+            .trim_start_matches('(')
+            .trim_end_matches(TRAILING_PUNCTUATION)
+            .to_string(),
+    };
+
+    // Placeholders are not values, they are flagged on their own
+    let lowercase = value.to_lowercase();
+    if value.is_empty()
+        || value.contains(['{', '<'])
+        || STOP_WORDS.contains(&lowercase.as_str())
+        || IDENTIFIER_WORDS.contains(&lowercase.as_str())
+    {
+        return None;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Parameter;
+
+    fn endpoint(sentence: &str) -> Endpoint {
+        Endpoint {
+            text: "Get order".to_string(),
+            suggested_sentence: sentence.to_string(),
+            verb: "GET".to_string(),
+            path: "/users/{userId}/orders/{order_id}".to_string(),
+            parameters: vec![
+                Parameter {
+                    name: "userId".to_string(),
+                    required: true,
+                    ..Default::default()
+                },
+                Parameter {
+                    name: "order_id".to_string(),
+                    required: true,
+                    alternatives: vec!["reference".to_string()],
+                    ..Default::default()
+                },
+                Parameter {
+                    name: "expand".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_sentences_with_a_value_for_every_required_parameter() {
+        assert!(sentence_problems(&endpoint("Show order 42 of user id 7")).is_empty());
+        assert!(sentence_problems(&endpoint("Get reference A-12 for userid 7")).is_empty());
+        assert!(sentence_problems(&endpoint("Get order 42 of user 7")).is_empty());
+        assert!(sentence_problems(&endpoint("Get order 'x 1' of user \"ann\"")).is_empty());
+    }
+
+    #[test]
+    fn flags_placeholders_and_omitted_parameters() {
+        assert_eq!(
+            sentence_problems(&endpoint("Get order {{order_id}} please")),
+            vec![
+                "contains placeholders {order_id} instead of example values",
+                "gives no example value for required parameters userId, order_id",
+            ]
+        );
+        assert_eq!(
+            sentence_problems(&endpoint("Get the order id for a user id")),
+            vec!["gives no example value for required parameters userId, order_id"]
+        );
+        assert_eq!(
+            sentence_problems(&endpoint("")),
+            vec!["missing suggested sentence"]
+        );
+    }
+
+    #[test]
+    fn applies_only_sentences_that_pass_the_check() {
+        let yaml = "api_groups:\n  - name: Orders\n    endpoints:\n      - text: Get order\n        verb: GET\n        path: /users/{userId}/orders/{order_id}\n        parameters:\n          - name: userId\n            required: true\n          - name: order_id\n            required: true\n      - text: List orders\n        verb: GET\n        path: /users/{userId}/orders\n        parameters:\n          - name: userId\n            required: true\n";
+        let catalog = ApiCatalog::from_yaml(yaml).unwrap();
+        let flagged = flagged_sentences(&catalog);
+        assert_eq!(flagged.len(), 2);
+
+        let answer = "- id: 0\n  suggested_sentence: Get the order id for a user id\n- id: 1\n  suggested_sentence: List the orders of user 7\n";
+        let (updated, replaced) = apply_sentences(yaml, &catalog, &flagged, answer).unwrap();
+        assert_eq!(replaced, 1);
+
+        let updated = ApiCatalog::from_yaml(&updated).unwrap();
+        let endpoints = &updated.api_groups[0].endpoints;
+        assert_eq!(endpoints[0].suggested_sentence, "");
+        assert_eq!(endpoints[1].suggested_sentence, "List the orders of user 7");
+    }
+}
@@ -0,0 +1,786 @@
+use graflog::app_log;
+use std::collections::HashMap;
+use yaml_rust2::parser::{Event, Parser};
+use yaml_rust2::scanner::Marker;
+
+/// Path of list keys leading to a list, e.g. `["api_groups", "endpoints"]`
+/// for the endpoints of a group. The root mapping has the empty path.
+type LevelPath = Vec<String>;
+
+/// Keys and indentation of the mappings held by one list of the template
+#[derive(Clone, Debug, Default)]
+struct Level {
+    keys: Vec<String>,
+    key_indent: usize,
+    /// Column of the `-` introducing each item, None for the root mapping
+    dash_indent: Option<usize>,
+}
+
+/// Nesting expected by the template: which keys live in which list and how
+/// deep each list is indented
+#[derive(Clone, Debug, Default)]
+pub struct TemplateSchema {
+    levels: HashMap<LevelPath, Level>,
+    /// Lists of plain values (e.g. `alternatives`) and the column of their `-`
+    scalar_lists: HashMap<LevelPath, usize>,
+}
+
+impl TemplateSchema {
+    pub fn from_template(template_content: &str) -> Self {
+        let scan = scan_events(template_content);
+        if let Some(error) = &scan.error {
+            app_log!(
+                warn,
+                "Template is not valid YAML, repairs will not use it: {}",
+                error.message
+            );
+        }
+        TemplateSchema {
+            levels: scan.levels,
+            scalar_lists: scan.scalar_lists,
+        }
+    }
+
+    /// The template schema, with the indentation the document already uses where known
+    fn adopt_indentation(&self, observed: &EventScan) -> TemplateSchema {
+        let mut schema = self.clone();
+        for (path, seen) in &observed.levels {
+            let level = schema.levels.entry(path.clone()).or_default();
+            level.key_indent = seen.key_indent;
+            level.dash_indent = seen.dash_indent.or(level.dash_indent);
+            for key in &seen.keys {
+                if !level.keys.contains(key) {
+                    level.keys.push(key.clone());
+                }
+            }
+        }
+        for (path, dash_indent) in &observed.scalar_lists {
+            schema.scalar_lists.insert(path.clone(), *dash_indent);
+        }
+        schema
+    }
+}
+
+struct ScanErrorAt {
+    /// 1-based line reported by the parser
+    line: usize,
+    message: String,
+}
+
+/// What the event stream revealed before the first error, if any
+#[derive(Default)]
+struct EventScan {
+    levels: HashMap<LevelPath, Level>,
+    scalar_lists: HashMap<LevelPath, usize>,
+    error: Option<ScanErrorAt>,
+    /// List items still open when the parser failed, outermost first: (list path, 1-based start line)
+    open_items: Vec<(LevelPath, usize)>,
+}
+
+enum Frame {
+    Mapping {
+        path: LevelPath,
+        start_line: usize,
+        is_item: bool,
+        /// Column of the `-` when the mapping is a block list item
+        dash_col: Option<usize>,
+        current_key: Option<String>,
+        expecting_key: bool,
+    },
+    Sequence {
+        path: LevelPath,
+        dash_col: Option<usize>,
+    },
+}
+
+/// Walks the YAML event stream, recording the keys and columns of every
+/// list of mappings, until the end of the document or the first error
+fn scan_events(content: &str) -> EventScan {
+    let chars: Vec<char> = content.chars().collect();
+    let lines: Vec<&str> = content.lines().collect();
+    let is_flow_start =
+        |marker: &Marker| matches!(chars.get(marker.index()), Some('[') | Some('{'));
+
+    let mut scan = EventScan::default();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut parser = Parser::new_from_str(content);
+
+    loop {
+        let (event, marker) = match parser.next_token() {
+            Ok(next) => next,
+            Err(e) => {
+                scan.error = Some(ScanErrorAt {
+                    line: e.marker().line(),
+                    message: e.info().to_string(),
+                });
+                scan.open_items = stack
+                    .iter()
+                    .filter_map(|frame| match frame {
+                        Frame::Mapping {
+                            path,
+                            start_line,
+                            is_item: true,
+                            ..
+                        } => Some((path.clone(), *start_line)),
+                        _ => None,
+                    })
+                    .collect();
+                return scan;
+            }
+        };
+
+        match event {
+            Event::StreamEnd => return scan,
+            Event::MappingStart(..) => {
+                let (path, is_item) = match stack.last() {
+                    None => (Vec::new(), false),
+                    Some(Frame::Sequence { path, .. }) => (path.clone(), true),
+                    Some(Frame::Mapping {
+                        path, current_key, ..
+                    }) => (child_path(path, current_key), false),
+                };
+                let dash_col = match stack.last() {
+                    Some(Frame::Sequence { dash_col, .. }) if is_item => *dash_col,
+                    _ => None,
+                };
+                stack.push(Frame::Mapping {
+                    path,
+                    start_line: marker.line(),
+                    is_item,
+                    dash_col,
+                    current_key: None,
+                    expecting_key: true,
+                });
+            }
+            Event::SequenceStart(..) => {
+                let path = match stack.last() {
+                    Some(Frame::Mapping {
+                        path, current_key, ..
+                    }) => child_path(path, current_key),
+                    Some(Frame::Sequence { path, .. }) => child_path(path, &Some("-".to_string())),
+                    None => Vec::new(),
+                };
+                // Indentless sequences are not marked at their `-`, read it from the line
+                let dash_col = lines
+                    .get(marker.line().saturating_sub(1))
+                    .filter(|line| !is_flow_start(&marker) && line.trim_start().starts_with('-'))
+                    .map(|line| indent_of(line));
+                stack.push(Frame::Sequence { path, dash_col });
+            }
+            Event::Scalar(value, ..) => match stack.last_mut() {
+                Some(Frame::Mapping {
+                    path,
+                    dash_col,
+                    current_key,
+                    expecting_key,
+                    ..
+                }) => {
+                    if *expecting_key {
+                        // Mapping events are marked at the `:`, the key scalar gives the column
+                        let level = scan.levels.entry(path.clone()).or_insert_with(|| Level {
+                            keys: Vec::new(),
+                            key_indent: marker.col(),
+                            dash_indent: *dash_col,
+                        });
+                        level.dash_indent = level.dash_indent.or(*dash_col);
+                        if !level.keys.contains(&value) {
+                            level.keys.push(value.clone());
+                        }
+                        *current_key = Some(value);
+                        *expecting_key = false;
+                    } else {
+                        *expecting_key = true;
+                    }
+                }
+                Some(Frame::Sequence {
+                    path,
+                    dash_col: Some(dash_col),
+                }) => {
+                    scan.scalar_lists.entry(path.clone()).or_insert(*dash_col);
+                }
+                _ => {}
+            },
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop();
+                if let Some(Frame::Mapping { expecting_key, .. }) = stack.last_mut() {
+                    *expecting_key = true;
+                }
+            }
+            Event::Alias(..) => {
+                if let Some(Frame::Mapping { expecting_key, .. }) = stack.last_mut() {
+                    *expecting_key = !*expecting_key;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn child_path(path: &LevelPath, key: &Option<String>) -> LevelPath {
+    let mut child = path.clone();
+    child.push(key.clone().unwrap_or_default());
+    child
+}
+
+/// Makes a document parse by re-indenting only the list items around each
+/// reported error, following the nesting of the template.
+///
+/// Returns None when the document still does not parse once the whole
+/// document has been re-indented.
+pub fn repair_indentation(yaml_content: &str, schema: &TemplateSchema) -> Option<String> {
+    let mut document = yaml_content.to_string();
+    let mut last_error_line = None;
+    let mut widen = 0;
+    let max_passes = document.lines().count() + 2;
+
+    for _ in 0..max_passes {
+        let scan = scan_events(&document);
+        let error = match &scan.error {
+            Some(error) => error,
+            None => return Some(document),
+        };
+
+        // The same error again means the window was too narrow, start from an outer item
+        if last_error_line == Some(error.line) {
+            widen += 1;
+        } else {
+            widen = 0;
+        }
+        if widen > scan.open_items.len() {
+            app_log!(
+                warn,
+                "Could not re-indent around line {}: {}",
+                error.line,
+                error.message
+            );
+            return None;
+        }
+
+        let (start_path, start_line) = match scan.open_items.len().checked_sub(widen + 1) {
+            Some(index) => scan.open_items[index].clone(),
+            None => (Vec::new(), 1),
+        };
+        app_log!(
+            info,
+            "Re-indenting from line {} around error at line {}: {}",
+            start_line,
+            error.line,
+            error.message
+        );
+
+        let effective = schema.adopt_indentation(&scan);
+        document = reindent_window(&document, &effective, &start_path, start_line, error.line);
+        last_error_line = Some(error.line);
+    }
+
+    None
+}
+
+enum LineKind<'a> {
+    Blank,
+    Comment,
+    /// `- key: value`
+    Item {
+        key: String,
+        value: &'a str,
+    },
+    /// `- value`
+    ScalarItem,
+    /// `key: value`
+    Entry {
+        key: String,
+        value: &'a str,
+    },
+    /// Continuation of a multi-line plain scalar
+    Other,
+}
+
+fn classify(line: &str) -> LineKind<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return LineKind::Blank;
+    }
+    if trimmed.starts_with('#') {
+        return LineKind::Comment;
+    }
+    if trimmed == "-" || trimmed.starts_with("- ") {
+        let after = trimmed[1..].trim_start();
+        return match split_key(after) {
+            Some((key, value)) => LineKind::Item { key, value },
+            None => LineKind::ScalarItem,
+        };
+    }
+    match split_key(trimmed) {
+        Some((key, value)) => LineKind::Entry { key, value },
+        None => LineKind::Other,
+    }
+}
+
+/// Splits `key: value` where key is a plain or quoted scalar
+fn split_key(text: &str) -> Option<(String, &str)> {
+    let (key, rest) = if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let end = text[1..].find(quote)? + 1;
+        (&text[1..end], text[end + 1..].trim_start())
+    } else {
+        let colon = text
+            .find(": ")
+            .or_else(|| text.strip_suffix(':').map(|k| k.len()))?;
+        let key = &text[..colon];
+        if key.is_empty() || key.contains(['{', '[', '"', '\'']) || key.contains(" #") {
+            return None;
+        }
+        (key, &text[colon..])
+    };
+    let value = rest.strip_prefix(':')?;
+    if !(value.is_empty() || value.starts_with(' ')) {
+        return None;
+    }
+    Some((key.trim().to_string(), value.trim()))
+}
+
+/// Lines following a key that must be moved along with it
+enum Continuation {
+    None,
+    /// Block scalar (`|` or `>`): lines indented deeper than the key
+    Block {
+        key_indent: usize,
+    },
+    /// Multi-line flow collection: lines until the brackets balance
+    Flow {
+        depth: i32,
+    },
+}
+
+fn flow_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '[') | (None, '{') => depth += 1,
+            (None, ']') | (None, '}') => depth -= 1,
+            (None, '#') => break,
+            _ => {}
+        }
+    }
+    depth
+}
+
+fn continuation_after(value: &str, key_indent: usize) -> Continuation {
+    if value.starts_with('|') || value.starts_with('>') {
+        return Continuation::Block { key_indent };
+    }
+    let depth = flow_depth(value);
+    if depth > 0 {
+        return Continuation::Flow { depth };
+    }
+    Continuation::None
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Re-indents the item starting at `start_line` and what follows it until the
+/// first correctly indented item at the same or an outer level after `error_line`
+fn reindent_window(
+    document: &str,
+    schema: &TemplateSchema,
+    start_path: &LevelPath,
+    start_line: usize,
+    error_line: usize,
+) -> String {
+    let lines: Vec<&str> = document.lines().collect();
+    let start = start_line.saturating_sub(1).min(lines.len());
+
+    // Levels currently open, innermost last
+    let mut stack: Vec<LevelPath> = (0..=start_path.len())
+        .map(|len| start_path[..len].to_vec())
+        .filter(|path| schema.levels.contains_key(path))
+        .collect();
+    let mut open_list: Option<LevelPath> = if start_path.is_empty() {
+        None
+    } else {
+        Some(start_path.clone())
+    };
+    let mut open_scalars: Option<LevelPath> = None;
+    let mut continuation = Continuation::None;
+    // (original indent, new indent) of the last key line, to carry its continuation lines
+    let mut last_shift = (0usize, 0usize);
+
+    let mut output: Vec<String> = lines[..start].iter().map(|line| line.to_string()).collect();
+    let mut index = start;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let line_number = index + 1;
+        let original_indent = indent_of(line);
+
+        match continuation {
+            Continuation::Block { key_indent } => {
+                if line.trim().is_empty() || original_indent > key_indent {
+                    output.push(shift_line(line, last_shift, None));
+                    index += 1;
+                    continue;
+                }
+                continuation = Continuation::None;
+            }
+            Continuation::Flow { depth } => {
+                let depth = depth + flow_depth(line);
+                output.push(shift_line(line, last_shift, Some(last_shift.1 + 2)));
+                continuation = if depth > 0 {
+                    Continuation::Flow { depth }
+                } else {
+                    Continuation::None
+                };
+                index += 1;
+                continue;
+            }
+            Continuation::None => {}
+        }
+
+        match classify(line) {
+            LineKind::Blank | LineKind::Comment => output.push(line.to_string()),
+            LineKind::Other => output.push(shift_line(line, last_shift, Some(last_shift.1 + 2))),
+            LineKind::ScalarItem => match open_scalars
+                .as_ref()
+                .and_then(|path| schema.scalar_lists.get(path))
+            {
+                Some(dash_indent) => {
+                    output.push(format!("{}{}", " ".repeat(*dash_indent), line.trim()));
+                }
+                None => output.push(line.to_string()),
+            },
+            LineKind::Item { key, value } => {
+                let lookahead = item_keys(&lines[index + 1..]);
+                let level_path = match open_list.take() {
+                    Some(child) if schema.levels.contains_key(&child) => Some(child),
+                    _ => choose_item_level(schema, &stack, &key, &lookahead),
+                };
+                let level_path = match level_path {
+                    Some(level_path) => level_path,
+                    None => {
+                        output.push(line.to_string());
+                        index += 1;
+                        continue;
+                    }
+                };
+                let level = &schema.levels[&level_path];
+                let dash_indent = level
+                    .dash_indent
+                    .unwrap_or_else(|| level.key_indent.saturating_sub(2));
+
+                // Past the error, a correctly placed sibling or outer item closes the window
+                if line_number > error_line
+                    && original_indent == dash_indent
+                    && start_path.starts_with(&level_path)
+                {
+                    output.extend(lines[index..].iter().map(|line| line.to_string()));
+                    break;
+                }
+
+                while stack
+                    .last()
+                    .is_some_and(|top| top.len() >= level_path.len())
+                {
+                    stack.pop();
+                }
+                stack.push(level_path.clone());
+
+                output.push(format!("{}{}", " ".repeat(dash_indent), line.trim()));
+                last_shift = (original_indent + 2, dash_indent + 2);
+                (open_list, open_scalars) = opened_list(schema, &level_path, &key, value);
+                continuation = continuation_after(value, original_indent + 2);
+            }
+            LineKind::Entry { key, value } => {
+                let position = stack.iter().rposition(|path| {
+                    schema
+                        .levels
+                        .get(path)
+                        .is_some_and(|level| level.keys.contains(&key))
+                });
+                let level_path = match position {
+                    Some(position) => {
+                        stack.truncate(position + 1);
+                        stack[position].clone()
+                    }
+                    None => {
+                        output.push(line.to_string());
+                        index += 1;
+                        continue;
+                    }
+                };
+                let key_indent = schema.levels[&level_path].key_indent;
+
+                output.push(format!("{}{}", " ".repeat(key_indent), line.trim()));
+                last_shift = (original_indent, key_indent);
+                (open_list, open_scalars) = opened_list(schema, &level_path, &key, value);
+                continuation = continuation_after(value, original_indent);
+            }
+        }
+        index += 1;
+    }
+
+    let mut repaired = output.join("\n");
+    if document.ends_with('\n') {
+        repaired.push('\n');
+    }
+    repaired
+}
+
+/// Moves a continuation line by the same amount as the key it belongs to
+fn shift_line(line: &str, (from, to): (usize, usize), minimum: Option<usize>) -> String {
+    if line.trim().is_empty() {
+        return line.to_string();
+    }
+    let indent = (indent_of(line) + to).saturating_sub(from);
+    let indent = minimum.map_or(indent, |minimum| indent.max(minimum));
+    format!("{}{}", " ".repeat(indent), line.trim_start())
+}
+
+/// The list a key with an empty value opens, as (list of mappings, list of values)
+fn opened_list(
+    schema: &TemplateSchema,
+    level_path: &LevelPath,
+    key: &str,
+    value: &str,
+) -> (Option<LevelPath>, Option<LevelPath>) {
+    if !value.is_empty() {
+        return (None, None);
+    }
+    let child = child_path(level_path, &Some(key.to_string()));
+    if schema.levels.contains_key(&child) {
+        (Some(child), None)
+    } else if schema.scalar_lists.contains_key(&child) {
+        (None, Some(child))
+    } else {
+        (None, None)
+    }
+}
+
+/// Keys of the plain `key: value` lines following an item start, up to the next item
+fn item_keys(lines: &[&str]) -> Vec<String> {
+    let mut keys = Vec::new();
+    for line in lines {
+        match classify(line) {
+            LineKind::Item { .. } => break,
+            LineKind::Entry { key, .. } => keys.push(key),
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// Picks the innermost open list whose items accept this key and the keys
+/// following it, so `- name:` starts a parameter or a group as appropriate
+fn choose_item_level(
+    schema: &TemplateSchema,
+    stack: &[LevelPath],
+    key: &str,
+    lookahead: &[String],
+) -> Option<LevelPath> {
+    let candidates: Vec<&LevelPath> = stack
+        .iter()
+        .rev()
+        .filter(|path| !path.is_empty())
+        .filter(|path| {
+            schema
+                .levels
+                .get(*path)
+                .is_some_and(|level| level.keys.iter().any(|k| k == key))
+        })
+        .collect();
+
+    candidates
+        .iter()
+        .find(|path| {
+            let level = &schema.levels[**path];
+            lookahead.iter().all(|k| level.keys.contains(k))
+        })
+        .or(candidates.first())
+        .map(|path| (*path).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> TemplateSchema {
+        TemplateSchema::from_template(&std::fs::read_to_string("template.yaml").unwrap())
+    }
+
+    fn repair(broken: &str) -> String {
+        assert!(serde_yaml::from_str::<serde_yaml::Value>(broken).is_err());
+        let repaired = repair_indentation(broken, &schema()).expect("repair failed");
+        serde_yaml::from_str::<serde_yaml::Value>(&repaired).expect("repaired YAML is invalid");
+        repaired
+    }
+
+    #[test]
+    fn learns_nesting_from_the_template() {
+        let schema = schema();
+        let endpoints = &schema.levels[&vec!["api_groups".to_string(), "endpoints".to_string()]];
+        assert_eq!(endpoints.dash_indent, Some(6));
+        assert_eq!(endpoints.key_indent, 8);
+        assert!(endpoints.keys.contains(&"suggested_sentence".to_string()));
+
+        let alternatives = vec![
+            "api_groups".to_string(),
+            "endpoints".to_string(),
+            "parameters".to_string(),
+            "alternatives".to_string(),
+        ];
+        assert_eq!(schema.scalar_lists[&alternatives], 14);
+    }
+
+    #[test]
+    fn leaves_valid_documents_untouched() {
+        let valid = std::fs::read_to_string("samples/output.yaml").unwrap();
+        assert_eq!(repair_indentation(&valid, &schema()), Some(valid));
+    }
+
+    #[test]
+    fn realigns_endpoint_key_with_its_siblings() {
+        let broken = "\
+api_groups:
+  - name: \"Users\"
+    endpoints:
+      - text: \"Get user\"
+        description: \"Retrieves a user\"
+       verb: \"GET\"
+        path: \"/users/{id}\"
+";
+        let repaired = repair(broken);
+        assert!(repaired.contains("\n        verb: \"GET\"\n"));
+    }
+
+    #[test]
+    fn moves_dedented_parameter_fields_back_into_the_parameter() {
+        let broken = "\
+api_groups:
+  - name: \"Users\"
+    endpoints:
+      - text: \"Get user\"
+        parameters:
+          - name: \"id\"
+            description: \"User identifier\"
+          required: true
+            alternatives:
+              - \"user_id\"
+      - text: \"List users\"
+        parameters: []
+";
+        let repaired = repair(broken);
+        let value: serde_yaml::Value = serde_yaml::from_str(&repaired).unwrap();
+        let parameter = &value["api_groups"][0]["endpoints"][0]["parameters"][0];
+        assert_eq!(parameter["required"], serde_yaml::Value::Bool(true));
+        assert_eq!(parameter["alternatives"][0].as_str(), Some("user_id"));
+        assert_eq!(
+            value["api_groups"][0]["endpoints"][1]["text"].as_str(),
+            Some("List users")
+        );
+    }
+
+    #[test]
+    fn starts_a_new_group_rather_than_a_parameter() {
+        let broken = "\
+api_groups:
+  - name: \"Users\"
+    endpoints:
+      - text: \"Get user\"
+        parameters:
+          - name: \"id\"
+            required: true
+   - name: \"Auth\"
+     description: \"Authentication\"
+     base: \"https://auth.example.com\"
+     endpoints:
+       - text: \"Login\"
+         verb: \"POST\"
+";
+        let repaired = repair(broken);
+        let value: serde_yaml::Value = serde_yaml::from_str(&repaired).unwrap();
+        assert_eq!(value["api_groups"][1]["name"].as_str(), Some("Auth"));
+        assert_eq!(
+            value["api_groups"][1]["endpoints"][0]["verb"].as_str(),
+            Some("POST")
+        );
+    }
+
+    #[test]
+    fn keeps_block_scalars_comments_and_flow_collections() {
+        let broken = "\
+api_groups:
+  - name: \"Users\"
+    endpoints:
+      - text: \"Get user\"
+         description: |
+           Retrieves a user.
+             Indented detail stays indented.
+        # the path below is templated
+        path: \"/users/{id}\"
+        parameters:
+          - { name: \"id\", required: true }
+          - name: \"fields\"
+            alternatives: [
+              \"columns\",
+              \"select\"
+            ]
+";
+        let repaired = repair(broken);
+        let value: serde_yaml::Value = serde_yaml::from_str(&repaired).unwrap();
+        let endpoint = &value["api_groups"][0]["endpoints"][0];
+        assert_eq!(
+            endpoint["description"].as_str(),
+            Some("Retrieves a user.\n  Indented detail stays indented.\n")
+        );
+        assert!(repaired.contains("        # the path below is templated\n"));
+        assert_eq!(endpoint["parameters"][0]["name"].as_str(), Some("id"));
+        assert_eq!(
+            endpoint["parameters"][1]["alternatives"][1].as_str(),
+            Some("select")
+        );
+    }
+
+    #[test]
+    fn follows_the_document_indentation_style() {
+        let broken = "\
+api_groups:
+- name: Users
+  endpoints:
+  - text: Get user
+    verb: GET
+     path: /users
+";
+        let repaired = repair(broken);
+        assert!(repaired.ends_with("  - text: Get user\n    verb: GET\n    path: /users\n"));
+    }
+
+    #[test]
+    fn only_touches_the_item_around_the_error() {
+        let untouched = "\
+api_groups:
+  - name: \"Users\"
+    endpoints:
+      - text: \"Get user\"
+        parameters:
+            - name: \"id\"
+              required: true
+";
+        let broken = format!(
+            "{}{}",
+            untouched,
+            "\
+  - name: \"Auth\"
+    endpoints:
+      - text: \"Login\"
+      verb: \"POST\"
+"
+        );
+        let repaired = repair(&broken);
+        assert!(repaired.starts_with(untouched));
+        assert!(repaired.ends_with("      - text: \"Login\"\n        verb: \"POST\"\n"));
+    }
+}
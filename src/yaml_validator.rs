@@ -1,8 +1,14 @@
 use graflog::app_log;
 use std::error::Error;
 
-/// Validates and fixes common YAML indentation issues
-pub fn validate_and_fix_yaml(yaml_content: &str) -> Result<String, Box<dyn Error>> {
+use crate::yaml_repair::{repair_indentation, TemplateSchema};
+
+/// Validates YAML and fixes indentation and duplicate key issues,
+/// re-indenting around parser errors according to the template schema
+pub fn validate_and_fix_yaml(
+    yaml_content: &str,
+    schema: &TemplateSchema,
+) -> Result<String, Box<dyn Error>> {
     // Parse the YAML content to check for errors
     match serde_yaml::from_str::<serde_yaml::Value>(yaml_content) {
         Ok(_) => {
@@ -13,9 +19,10 @@ pub fn validate_and_fix_yaml(yaml_content: &str) -> Result<String, Box<dyn Error
             app_log!(warn, "YAML validation failed: {}", e);
             app_log!(info, "Attempting to fix YAML indentation issues");
 
-            // Fix indentation issues
-            let indented_yaml = fix_yaml_indentation(yaml_content);
-            
+            // Fix indentation issues around the errors reported by the parser
+            let indented_yaml = repair_indentation(yaml_content, schema)
+                .unwrap_or_else(|| yaml_content.to_string());
+
             // Fix duplicate keys (common issue with LLM output)
             let fixed_yaml = fix_duplicate_keys(&indented_yaml);
 
@@ -34,75 +41,33 @@ pub fn validate_and_fix_yaml(yaml_content: &str) -> Result<String, Box<dyn Error
     }
 }
 
-/// Fixes common YAML indentation issues
-fn fix_yaml_indentation(yaml_content: &str) -> String {
-    let lines: Vec<&str> = yaml_content.lines().collect();
-    let mut fixed_lines = Vec::new();
-    let mut current_indent_level = 0;
-    let indent_size = 2; // Standard YAML indent size
-
-    // Detect root level items (usually api_groups:)
-    let root_items: Vec<&str> = lines
-        .iter()
-        .filter(|line| {
-            !line.trim().is_empty()
-                && !line.trim().starts_with('-')
-                && !line.trim().starts_with('#')
-        })
-        .filter(|line| !line.trim_start().starts_with('-'))
-        .map(|line| line.trim().split(':').next().unwrap_or(""))
-        .filter(|item| !item.is_empty())
+/// Describes why a YAML document does not parse, with the lines around the error.
+/// Returns None when the document is valid.
+pub fn describe_yaml_error(yaml_content: &str) -> Option<String> {
+    let error = serde_yaml::from_str::<serde_yaml::Value>(yaml_content).err()?;
+
+    let location = match error.location() {
+        Some(location) => location,
+        None => return Some(error.to_string()),
+    };
+
+    // Show two lines of context on each side of the reported line
+    let first = location.line().saturating_sub(3);
+    let excerpt: Vec<String> = yaml_content
+        .lines()
+        .enumerate()
+        .skip(first)
+        .take(5)
+        .map(|(index, line)| format!("{:>4} | {}", index + 1, line))
         .collect();
 
-    for line in lines {
-        let trimmed = line.trim();
-
-        if trimmed.is_empty() {
-            fixed_lines.push(String::new());
-            continue;
-        }
-
-        // Handle root level items
-        if root_items.contains(&trimmed.split(':').next().unwrap_or(""))
-            && !trimmed.starts_with('-')
-        {
-            current_indent_level = 0;
-            fixed_lines.push(trimmed.to_string());
-            continue;
-        }
-
-        // Handle list items
-        if trimmed.starts_with('-') {
-            // Determine if this is a new list start or continuation
-            if !fixed_lines.is_empty() {
-                let prev_line = fixed_lines.last().unwrap().trim();
-                if prev_line.ends_with(':') {
-                    // This is a list item after a property, indent more
-                    current_indent_level += 1;
-                }
-            }
-
-            let spaces = " ".repeat(current_indent_level * indent_size);
-            fixed_lines.push(format!("{}{}", spaces, trimmed));
-            continue;
-        }
-
-        // Handle properties inside list items
-        if trimmed.contains(':') && !trimmed.ends_with(':') {
-            let spaces = " ".repeat((current_indent_level + 1) * indent_size);
-            fixed_lines.push(format!("{}{}", spaces, trimmed));
-        } else if trimmed.ends_with(':') {
-            // Property that will contain more nested items
-            let spaces = " ".repeat(current_indent_level * indent_size);
-            fixed_lines.push(format!("{}{}", spaces, trimmed));
-        } else {
-            // Other lines, preserve as is but with proper indentation
-            let spaces = " ".repeat(current_indent_level * indent_size);
-            fixed_lines.push(format!("{}{}", spaces, trimmed));
-        }
-    }
-
-    fixed_lines.join("\n")
+    Some(format!(
+        "{} (line {}, column {})\n{}",
+        error,
+        location.line(),
+        location.column(),
+        excerpt.join("\n")
+    ))
 }
 
 use std::collections::HashSet;
@@ -174,23 +139,3 @@ fn fix_duplicate_keys(yaml_content: &str) -> String {
     
     lines.join("\n")
 }
-
-// Alternative approach: Use YAML parser to properly format the YAML
-// pub fn reformat_yaml(yaml_content: &str) -> Result<String, Box<dyn Error>> {
-//     // First try to parse the YAML
-//     let yaml_value: serde_yaml::Value = match serde_yaml::from_str(yaml_content) {
-//         Ok(value) => value,
-//         Err(e) => {
-//             // If parsing fails, try basic fixing first
-//             let fixed_yaml = fix_yaml_indentation(yaml_content);
-//             match serde_yaml::from_str(&fixed_yaml) {
-//                 Ok(value) => value,
-//                 Err(_) => return Err(format!("Failed to parse YAML: {}", e).into()),
-//             }
-//         }
-//     };
-//
-//     // Then convert back to a properly formatted string
-//     let formatted_yaml = serde_yaml::to_string(&yaml_value)?;
-//     Ok(formatted_yaml)
-// }